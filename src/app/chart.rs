@@ -0,0 +1,232 @@
+//! Charts printed by `--chart`
+//!
+//! Charts are drawn with ratatui into an off-screen buffer as wide as the terminal, then printed as plain text.
+
+use std::{borrow::Cow, str::FromStr};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    symbols,
+    widgets::{Axis, Chart, Dataset, GraphType, Widget}
+};
+use weer_api::ForecastDay;
+use super::{config::Units, locale::text};
+
+
+/// Names accepted by `--chart`
+pub const METRICS: [&str; 5] = ["temp", "precip", "wind", "humidity", "uv"];
+
+/// Rows of the plot area, the x axis and its labels come on top of them
+const HEIGHT: u16 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Metric {
+    Temperature,
+    Precipitation,
+    Wind,
+    Humidity,
+    Uv
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "temp" => Ok(Metric::Temperature),
+            "precip" => Ok(Metric::Precipitation),
+            "wind" => Ok(Metric::Wind),
+            "humidity" => Ok(Metric::Humidity),
+            "uv" => Ok(Metric::Uv),
+            _ => Err(format!("expected one of: {}", METRICS.join(", ")))
+        }
+    }
+}
+
+impl Metric {
+    fn title(&self) -> Cow<'static, str> {
+        text(match self {
+            Metric::Temperature => "temperature",
+            Metric::Precipitation => "precipitation",
+            Metric::Wind => "wind",
+            Metric::Humidity => "humidity",
+            Metric::Uv => "uv-index"
+        })
+    }
+
+    /// Decimals of the axis labels
+    fn precision(&self, units: Units) -> usize {
+        match (self, units) {
+            (Metric::Precipitation, Units::Imperial) => 2,
+            _ => 1
+        }
+    }
+
+    fn unit(&self, units: Units) -> &'static str {
+        match (self, units) {
+            (Metric::Temperature, Units::Metric) => "°C",
+            (Metric::Temperature, Units::Imperial) => "°F",
+            (Metric::Precipitation, Units::Metric) => "mm",
+            (Metric::Precipitation, Units::Imperial) => "in",
+            (Metric::Wind, Units::Metric) => "km/h",
+            (Metric::Wind, Units::Imperial) => "mph",
+            (Metric::Humidity, _) => "%",
+            (Metric::Uv, _) => ""
+        }
+    }
+
+    /// Whether zero is a natural bottom of the y axis
+    fn starts_at_zero(&self) -> bool {
+        !matches!(self, Metric::Temperature)
+    }
+}
+
+/// Values of `metric` across the hours of `days`, or across the days when the response has no hourly data
+fn series(days: &[ForecastDay], metric: Metric, units: Units) -> Vec<(String, f64)> {
+    let imperial = units == Units::Imperial;
+    let hourly = days.iter().all(|fd| fd.hour.len() > 1);
+
+    if hourly {
+        days.iter()
+            .flat_map(|fd| fd.hour.iter())
+            .map(|h| {
+                let value = match metric {
+                    Metric::Temperature => if imperial { h.temp_f } else { h.temp_c },
+                    Metric::Precipitation => if imperial { h.precip_in } else { h.precip_mm },
+                    Metric::Wind => if imperial { h.wind_mph } else { h.wind_kph },
+                    Metric::Humidity => h.humidity as f32,
+                    Metric::Uv => h.uv
+                };
+
+                // `yyyy-mm-dd hh:mm`, the year only widens the axis labels
+                (h.time.get(5..).unwrap_or(&h.time).to_string(), value as f64)
+            })
+            .collect()
+    } else {
+        days.iter()
+            .map(|fd| {
+                let value = match metric {
+                    Metric::Temperature => if imperial { fd.day.avgtemp_f } else { fd.day.avgtemp_c },
+                    Metric::Precipitation => if imperial { fd.day.totalprecip_in } else { fd.day.totalprecip_mm },
+                    Metric::Wind => if imperial { fd.day.maxwind_mph } else { fd.day.maxwind_kph },
+                    Metric::Humidity => fd.day.avghumidity,
+                    Metric::Uv => fd.day.uv
+                };
+
+                (fd.date.clone(), value as f64)
+            })
+            .collect()
+    }
+}
+
+/// Chart of `metric` fitting in `width` columns, `None` when there is nothing to draw
+pub fn chart(days: &[ForecastDay], metric: Metric, units: Units, width: u16) -> Option<String> {
+    let series = series(days, metric, units);
+    let (first, last) = (series.first()?, series.last()?);
+
+    let points: Vec<(f64, f64)> = series.iter()
+        .enumerate()
+        .map(|(i, (_, value))| (i as f64, *value))
+        .collect();
+
+    let values = points.iter().map(|p| p.1);
+    let mut min = values.clone().fold(f64::INFINITY, f64::min);
+    let mut max = values.fold(f64::NEG_INFINITY, f64::max);
+    if metric.starts_at_zero() {
+        min = min.min(0.0);
+    }
+    if (max - min).abs() < f64::EPSILON {
+        max = min + 1.0;
+    }
+
+    let unit = metric.unit(units);
+    let precision = metric.precision(units);
+    let label = |v: f64| format!("{:.*}{}", precision, v, unit);
+    let x_max = (points.len() - 1).max(1) as f64;
+
+    // A middle label of two points would be the last one again, drawn over it
+    let mut x_labels = vec![first.0.clone(), last.0.clone()];
+    if series.len() > 2 {
+        x_labels.insert(1, series[series.len() / 2].0.clone());
+    }
+
+    let dataset = Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(match metric {
+            Metric::Precipitation => GraphType::Bar,
+            _ => GraphType::Line
+        })
+        .data(&points);
+
+    let chart = Chart::new(vec![dataset])
+        .x_axis(Axis::default()
+            .bounds([0.0, x_max])
+            .labels(x_labels))
+        .y_axis(Axis::default()
+            .bounds([min, max])
+            .labels([label(min), label((min + max) / 2.0), label(max)]));
+
+    let area = Rect::new(0, 0, width, HEIGHT + 2);
+    let mut buf = Buffer::empty(area);
+    chart.render(area, &mut buf);
+
+    let mut out = match unit {
+        "" => format!("{}\n", metric.title()),
+        unit => format!("{} ({})\n", metric.title(), unit)
+    };
+    for row in buf.content().chunks(area.width as usize) {
+        let line: String = row.iter().map(|cell| cell.symbol()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+
+    Some(out)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weer_api::{Forecast, mock::fixtures};
+
+    fn days() -> Vec<ForecastDay> {
+        serde_json::from_str::<Forecast>(fixtures::FORECAST).unwrap().forecast.forecast_day
+    }
+
+    #[test]
+    fn hourly_chart() {
+        let temperature = chart(&days(), Metric::Temperature, Units::Metric, 60).unwrap();
+        let lines: Vec<&str> = temperature.lines().collect();
+
+        assert_eq!(HEIGHT as usize + 3, lines.len());
+        assert!(lines.iter().all(|line| line.chars().count() <= 60));
+        assert!(lines[0].ends_with("(°C)"));
+
+        // The y axis spans the coldest to the warmest hour, the x axis both days
+        assert!(lines[1].starts_with("16.0°C"));
+        assert!(lines[HEIGHT as usize / 2 + 1].starts_with("10.5°C"));
+        assert!(lines[HEIGHT as usize].starts_with("5.0°C"));
+        let x_labels = lines.last().unwrap();
+        assert!(x_labels.starts_with("10-19 00:00") && x_labels.contains(" 10-20 00:00 ") && x_labels.ends_with("10-20 23:00"));
+
+        let precip = chart(&days(), Metric::Precipitation, Units::Imperial, 60).unwrap();
+        assert!(precip.lines().any(|line| line.starts_with("0.00in")));
+    }
+
+    #[test]
+    fn without_hours() {
+        let mut days = days();
+        days.iter_mut().for_each(|fd| fd.hour.clear());
+
+        // One point per day
+        let humidity = chart(&days, Metric::Humidity, Units::Metric, 40).unwrap();
+        let x_labels = humidity.lines().last().unwrap();
+        assert!(x_labels.starts_with("2023-10-19") && x_labels.ends_with(" 2023-10-20"));
+        assert_eq!(2, x_labels.matches("2023-10-").count());
+
+        days.truncate(1);
+        assert!(chart(&days, Metric::Uv, Units::Metric, 40).is_some());
+        assert!(chart(&[], Metric::Wind, Units::Metric, 40).is_none());
+    }
+}
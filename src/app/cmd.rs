@@ -1,58 +1,218 @@
-use clap::{Command, command, ArgAction, arg};
+use std::{net::IpAddr, path::PathBuf};
+use clap::{Arg, Command, command, ArgAction, arg, value_parser, builder::{PossibleValuesParser, TypedValueParser}};
+use weer_api::DegreeDayMethod;
+use clap_complete::Shell;
+use super::{chart, completions, config, format, notify::Rule};
 
 pub fn build<'a>() -> Command<'a> {
     command!()
         .args(&[
-            arg!(--city [name])
+            arg!(--city <name> "Location name, repeat it to compare locations")
+                .action(ArgAction::Append)
+                .required(false)
                 .conflicts_with_all(&[
                     "ip",
                     "coords"
                 ]),
             arg!(--ip [ip_addr])
+                .value_parser(value_parser!(IpAddr))
                 .conflicts_with_all(&[
                     "city",
                     "coords"
                 ]),
-            arg!(--coords [coords])
+            arg!(--coords <coords> "Latitude and longitude, repeat it to compare locations")
+                .action(ArgAction::Append)
+                .required(false)
+                .value_parser(parse_coords)
                 .conflicts_with_all(&[
                     "city",
                     "ip"
-                ])
+                ]),
+            arg!(--"api-key-file" [path] "Read the api key from a file")
+                .value_parser(value_parser!(PathBuf))
+                .global(true),
+            arg!(--profile [name] "Use a named profile, also set with WEER_PROFILE")
+                .global(true),
+            arg!(--record <file> "Record the requests and responses to a file, without the api key")
+                .required(false)
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with("replay")
+                .global(true),
+            arg!(--replay <file> "Answer the requests from a recorded file instead of the API")
+                .required(false)
+                .value_parser(value_parser!(PathBuf))
+                .global(true),
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .help("Print the requests to stderr, -vv for every attempt")
+                .action(ArgAction::Count)
+                .global(true)
         ])
         .arg_required_else_help(true)
+        .after_help(EXIT_CODES)
         .subcommands(applet_commands())
 }
 
-fn applet_commands<'a>() -> [Command<'a>; 5] {
+const EXIT_CODES: &str = "EXIT CODES:
+    64    Invalid command line
+    65    Unknown location or malformed response
+    69    Weather API unreachable
+    70    Internal error
+    74    Output or file not readable or writable
+    77    Api key rejected or quota exceeded
+    78    Invalid config or no api key";
+
+fn applet_commands<'a>() -> [Command<'a>; 18] {
     [
+        now(),
         forecast(),
         history(),
+        stats(),
+        degree_days(),
+        agro(),
         config(),
+        profile(),
+        watch(),
+        dashboard(),
+        notify(),
+        Command::new("languages")
+            .about("List the languages of the condition texts"),
         Command::new("search"),
+        Command::new("quota")
+            .about("Show the calls made with the api key in the current billing period"),
         Command::new("future")
-            .arg(arg!(--dt <date>)),
+            .args(&[arg!(--dt <date>), chart_arg()]),
+        Command::new("completions")
+            .about("Print the completion script of a shell")
+            .arg(arg!(<shell> "Shell to complete")
+                .value_parser(value_parser!(Shell))),
+        Command::new("man")
+            .about("Print the man page, or write one page per command to a directory")
+            .arg(arg!(--dir [dir] "Directory of the pages")
+                .value_parser(value_parser!(PathBuf))),
+        // Called by the completion scripts
+        Command::new("complete")
+            .hide(true)
+            .arg(arg!(<kind>)
+                .value_parser(PossibleValuesParser::new(completions::KINDS))),
     ]
 }
 
 fn config<'a>() -> Command<'a> {
+    let key = || arg!(<key> "Config key")
+        .value_parser(PossibleValuesParser::new(config::KEYS));
+
     Command::new("config")
-        .args(&[
-            arg!(--lang [lang]),
-            arg!(--api_key [api_key]),
+        .about("Manage the configuration")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommands([
+            Command::new("get")
+                .about("Print the value of a key")
+                .arg(key()),
+            Command::new("set")
+                .about("Set the value of a key")
+                .args(&[key(), arg!(<value> "New value")]),
+            Command::new("unset")
+                .about("Reset a key to its default or inherited value")
+                .arg(key()),
+            Command::new("list")
+                .about("Print every key"),
+            Command::new("path")
+                .about("Print the path of the config file"),
+            Command::new("reset")
+                .about("Reset every key of the profile"),
+            Command::new("validate")
+                .about("Check the api key against the API"),
         ])
+}
+
+fn profile<'a>() -> Command<'a> {
+    Command::new("profile")
+        .about("Manage named profiles")
+        .subcommand_required(true)
         .arg_required_else_help(true)
+        .subcommands([
+            Command::new("list")
+                .about("Print every profile"),
+            Command::new("create")
+                .about("Create a profile that inherits from the default one")
+                .arg(arg!(<name> "Profile name")),
+            Command::new("copy")
+                .about("Create a profile with the settings of another one")
+                .args(&[arg!(<src> "Profile to copy"), arg!(<dst> "New profile name")]),
+            Command::new("delete")
+                .about("Delete a profile")
+                .arg(arg!(<name> "Profile name")),
+        ])
+}
+
+pub fn now<'a>() -> Command<'a> {
+    Command::new("now")
+        .about("Current weather")
+        .args(&[
+            arg!(--aqi)
+                .action(ArgAction::SetTrue),
+            arg!(--format <format> "One line made of placeholders, e.g: \"%c %t (%f) %w %h\"")
+                .required(false),
+            arg!(--preset <preset> "Print the line as JSON for a status bar")
+                .required(false)
+                .value_parser(PossibleValuesParser::new(format::PRESETS)),
+            arg!(--color <markup> "Colour the temperatures of the line")
+                .required(false)
+                .default_value("none")
+                .value_parser(PossibleValuesParser::new(format::COLORS)),
+        ])
+}
+
+fn watch<'a>() -> Command<'a> {
+    Command::new("watch")
+        .about("Redraw the current weather or the forecast in place every few minutes")
+        .arg(arg!(-i --interval <minutes> "Minutes between refreshes, defaults to the watch_interval setting")
+            .required(false)
+            .value_parser(parse_interval))
+        .subcommands([now(), forecast()])
+}
+
+fn dashboard<'a>() -> Command<'a> {
+    Command::new("dashboard")
+        .about("Full-screen dashboard of the saved locations")
+        .arg(arg!(-d --days <num> "Days of forecast")
+            .required(false)
+            .default_value("3")
+            .value_parser(value_parser!(u8).range(1..=14)))
+}
+
+fn notify<'a>() -> Command<'a> {
+    Command::new("notify")
+        .about("Check rules against the forecast and report the new events")
+        .args(&[
+            arg!(-r --rule <rule> "e.g: \"chance_of_rain > 60 within 3h\", \"temp_c < 0 tomorrow 06:00-09:00\" or \"alert flood\"")
+                .action(ArgAction::Append)
+                .value_parser(value_parser!(Rule)),
+            arg!(--desktop "Show a desktop notification, the default without --exec and --webhook")
+                .action(ArgAction::SetTrue),
+            arg!(--exec <command> "Run a shell command with the event in WEER_* environment variables")
+                .required(false),
+            arg!(--webhook <url> "POST the event as JSON")
+                .required(false),
+        ])
 }
 
 fn forecast<'a>() -> Command<'a> {
     Command::new("forecast")
         .args(&[
             arg!(--dt [date]),
-            arg!(-d --days <num>),
-            arg!(--hour <hour>),
+            arg!(-d --days <num>)
+                .value_parser(value_parser!(u8)),
+            arg!(--hour [hour])
+                .value_parser(value_parser!(u32)),
             arg!(--aqi)
                 .action(ArgAction::SetTrue),
             arg!(--alerts)
                 .action(ArgAction::SetTrue),
+            chart_arg(),
         ])
 }
 
@@ -61,6 +221,80 @@ fn history<'a>() -> Command<'a> {
         .args(&[
             arg!(--dt <date>),
             arg!(--end_dt [date]),
-            arg!(-h --hour [hour]),
+            arg!(-H --hour [hour])
+                .value_parser(value_parser!(u32)),
+            chart_arg(),
+        ])
+}
+
+fn stats<'a>() -> Command<'a> {
+    Command::new("stats")
+        .about("Summarise the history of a span of days, per month and for the whole span")
+        .args(&[
+            arg!(--from <date> "First day, e.g: 2023-01-01"),
+            arg!(--to <date> "Last day, included"),
+            arg!(--json "Print the summaries as JSON")
+                .action(ArgAction::SetTrue),
+        ])
+}
+
+fn degree_days<'a>() -> Command<'a> {
+    Command::new("degree-days")
+        .about("Heating and cooling degree days of a span of days, with their running totals")
+        .args(&[
+            arg!(--from <date> "First day, e.g: 2023-01-01"),
+            arg!(--to <date> "Last day, included"),
+            arg!(--"heating-base" <temp> "Base temperature of the heating degree days, 18°C or 65°F by default")
+                .required(false)
+                .value_parser(value_parser!(f32)),
+            arg!(--"cooling-base" <temp> "Base temperature of the cooling degree days, 18°C or 65°F by default")
+                .required(false)
+                .value_parser(value_parser!(f32)),
+            arg!(--method <method> "How the mean temperature of a day is taken")
+                .required(false)
+                .default_value("min-max")
+                .value_parser(PossibleValuesParser::new(["min-max", "average", "hourly"]).map(|m| match m.as_str() {
+                    "average" => DegreeDayMethod::Average,
+                    "hourly" => DegreeDayMethod::Hourly,
+                    _ => DegreeDayMethod::MinMax
+                })),
+            arg!(--csv "Print the days as CSV")
+                .action(ArgAction::SetTrue),
         ])
 }
+
+fn agro<'a>() -> Command<'a> {
+    Command::new("agro")
+        .about("Growing degree days and reference evapotranspiration of a span of days")
+        .args(&[
+            arg!(--from <date> "First day, e.g: 2023-04-01"),
+            arg!(--to <date> "Last day, included"),
+            arg!(--base <temp> "Temperature below which the crop doesn't grow, 10°C or 50°F by default")
+                .required(false)
+                .value_parser(value_parser!(f32)),
+            arg!(--cap <temp> "Temperature above which the crop grows no faster, 30°C or 86°F by default")
+                .required(false)
+                .value_parser(value_parser!(f32)),
+            arg!(--"no-cap" "Don't cap the temperatures")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("cap"),
+        ])
+}
+
+fn chart_arg<'a>() -> Arg<'a> {
+    arg!(--chart <metric> "Draw a chart of the hours, or of the days without hourly data")
+        .required(false)
+        .value_parser(PossibleValuesParser::new(chart::METRICS))
+}
+
+fn parse_interval(s: &str) -> Result<u64, String> {
+    config::parse_watch_interval(s).map_err(|e| e.to_string())
+}
+
+fn parse_coords(s: &str) -> Result<(f32, f32), String> {
+    let (lat, lon) = s.split_once(',')
+        .ok_or_else(|| "expected coordinates as `lat,lon`".to_string())?;
+    let lat = lat.trim().parse::<f32>().map_err(|e| e.to_string())?;
+    let lon = lon.trim().parse::<f32>().map_err(|e| e.to_string())?;
+    Ok((lat, lon))
+}
@@ -0,0 +1,170 @@
+//! Shell completions and man pages generated from `cmd::build`
+//!
+//! The scripts of bash, zsh and fish also ask `weer complete` for the saved locations and the language codes, so
+//! they follow the config instead of what it held when the script was generated.
+
+use std::{
+    fs,
+    error::Error,
+    io::{self, Write},
+    path::Path
+};
+
+use clap::Command;
+use clap_complete::Shell;
+use clap_mangen::Man;
+use weer_api::Language;
+use super::{cmd, ConfigFile};
+
+
+const BIN: &str = "weer";
+
+/// Values offered by `weer complete`
+pub const KINDS: [&str; 2] = ["locations", "languages"];
+
+const BASH: &str = r#"
+_weer_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}" prev="${COMP_WORDS[COMP_CWORD-1]}" kind=""
+
+    if [[ "$prev" == "--city" ]]; then
+        kind=locations
+    elif [[ "$prev" == "lang" && "${COMP_WORDS[*]}" == *" config set "* ]]; then
+        kind=languages
+    fi
+
+    if [[ -z "$kind" ]]; then
+        _weer "$@"
+        return
+    fi
+
+    local value
+    COMPREPLY=()
+    while IFS=$'\t' read -r value _; do
+        if [[ "$value" == "$cur"* ]]; then
+            COMPREPLY+=("$(printf '%q' "$value")")
+        fi
+    done < <(weer complete "$kind" 2>/dev/null)
+}
+
+complete -F _weer_dynamic -o bashdefault -o default weer
+"#;
+
+const ZSH: &str = r#"
+(( $+functions[_weer_locations] )) ||
+_weer_locations() {
+    local -a locations
+    locations=("${(@f)$(weer complete locations 2>/dev/null)}")
+    compadd -a locations
+}
+
+(( $+functions[_weer_values] )) ||
+_weer_values() {
+    if [[ "${words[CURRENT-1]}" == "lang" ]]; then
+        local -a languages
+        languages=("${(@f)$(weer complete languages 2>/dev/null | sed 's/:/\\:/g; s/\t/:/')}")
+        _describe 'language' languages
+    fi
+}
+"#;
+
+const FISH: &str = r#"
+complete -c weer -l city -f -a "(weer complete locations 2>/dev/null)"
+complete -c weer -n "__fish_seen_subcommand_from config; and __fish_seen_subcommand_from set; and __fish_seen_subcommand_from lang" -f -a "(weer complete languages 2>/dev/null)"
+"#;
+
+pub fn generate(shell: Shell, out: &mut impl Write) -> io::Result<()> {
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut cmd::build(), BIN, &mut script);
+    let script = String::from_utf8_lossy(&script);
+
+    match shell {
+        Shell::Bash => write!(out, "{}{}", script, BASH),
+        Shell::Zsh => {
+            let script = script
+                .replace("compare locations]:name: '", "compare locations]:name:_weer_locations'")
+                .replace(":value -- New value:'", ":value -- New value:_weer_values'");
+            // The helpers must be defined before the last line of the script runs `_weer`
+            match script.rfind("\n_weer \"$@\"") {
+                Some(i) => write!(out, "{}{}{}", &script[..i], ZSH, &script[i..]),
+                None => write!(out, "{}{}", script, ZSH)
+            }
+        }
+        Shell::Fish => write!(out, "{}{}", script, FISH),
+        _ => write!(out, "{}", script)
+    }
+}
+
+/// Prints the page of `weer`, or writes one page per command to `dir`
+pub fn man(dir: Option<&Path>, out: &mut impl Write) -> Result<(), Box<dyn Error>> {
+    let cmd = cmd::build();
+
+    let dir = match dir {
+        Some(dir) => dir,
+        None => return Ok(Man::new(cmd).render(out)?)
+    };
+
+    fs::create_dir_all(dir)?;
+    write_page(dir, BIN, cmd.clone())?;
+    for sub in cmd.get_subcommands().filter(|sub| !sub.is_hide_set()) {
+        let name = format!("{}-{}", BIN, sub.get_name());
+        write_page(dir, &name, sub.clone().name(name.clone()))?;
+    }
+
+    Ok(())
+}
+
+fn write_page(dir: &Path, name: &str, cmd: Command) -> Result<(), Box<dyn Error>> {
+    let mut page = Vec::new();
+    Man::new(cmd).render(&mut page)?;
+    fs::write(dir.join(format!("{}.1", name)), page)?;
+    Ok(())
+}
+
+/// Values of `weer complete`, one per line, language codes are followed by their name after a tab
+pub fn values(kind: &str, file: &ConfigFile, out: &mut impl Write) -> io::Result<()> {
+    match kind {
+        "locations" => {
+            let mut locations: Vec<&String> = Some(&file.default).into_iter()
+                .chain(file.profiles.values())
+                .flat_map(|cfg| cfg.location.iter().chain(cfg.locations.iter().flatten()))
+                .collect();
+            locations.sort();
+            locations.dedup();
+
+            for location in locations {
+                writeln!(out, "{}", location)?;
+            }
+        }
+        "languages" => {
+            for lang in Language::all() {
+                writeln!(out, "{}\t{}", lang.content(), lang.name())?;
+            }
+        }
+        _ => unreachable!("complete takes one of KINDS")
+    }
+
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dynamic_values() {
+        let script = |shell| {
+            let mut out = Vec::new();
+            generate(shell, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+
+        // Both replacements must still match the output of clap_complete
+        let zsh = script(Shell::Zsh);
+        assert!(zsh.contains("compare locations]:name:_weer_locations'"));
+        assert!(zsh.contains(":value -- New value:_weer_values'"));
+        assert!(zsh.find("_weer_values() {") < zsh.rfind("\n_weer \"$@\""));
+
+        assert!(script(Shell::Bash).ends_with("complete -F _weer_dynamic -o bashdefault -o default weer\n"));
+    }
+}
@@ -0,0 +1,745 @@
+use std::{
+    collections::BTreeMap,
+    env,
+    fs::{self, OpenOptions},
+    error::Error,
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration
+};
+use directories::ProjectDirs;
+use serde::{Serialize, Deserialize};
+use weer_api::{Language, Cache, Client, LimitAction, Quota, RateLimiter};
+use super::locale::{self, text};
+
+const APP_NAME: &str = "weer";
+const CONFIG_NAME: &str = "config";
+
+/// Environment variable that takes precedence over any other api key source
+pub const API_KEY_ENV: &str = "WEER_API_KEY";
+
+/// Environment variable that selects a profile
+pub const PROFILE_ENV: &str = "WEER_PROFILE";
+
+/// Profile every other profile inherits from
+pub const DEFAULT_PROFILE: &str = "default";
+
+
+/// Every key handled by `weer config`
+pub const KEYS: [&str; 20] = [
+    "api_key",
+    "lang",
+    "units",
+    "location",
+    "locations",
+    "icons",
+    "cache_ttl_current",
+    "cache_ttl_forecast",
+    "cache_ttl_history",
+    "watch_interval",
+    "rate_limit",
+    "quota_budget",
+    "quota_billing_day",
+    "quota_soft_limit",
+    "quota_action",
+    "timeout_connect",
+    "timeout_read",
+    "proxy",
+    "no_proxy",
+    "root_certificates"
+];
+
+/// Shortest refresh interval of `weer watch` in minutes, keeps it within the free plan quota
+pub const MIN_WATCH_INTERVAL: u64 = 5;
+
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Units {
+    #[default]
+    Metric,
+    Imperial
+}
+
+impl FromStr for Units {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "metric" | "c" | "celsius" => Ok(Units::Metric),
+            "imperial" | "f" | "fahrenheit" => Ok(Units::Imperial),
+            _ => Err(locale::format("error-unknown-units", &[("units", &s)]))
+        }
+    }
+}
+
+impl Display for Units {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Units::Metric => write!(f, "metric"),
+            Units::Imperial => write!(f, "imperial")
+        }
+    }
+}
+
+
+/// Settings stored in the config file
+///
+/// Unset values fall back to their defaults through the accessor methods.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Config {
+    pub lang: Option<Language>,
+    pub api_key: Option<String>,
+    pub units: Option<Units>,
+    /// Location used when neither `--city`, `--ip` nor `--coords` are given
+    pub location: Option<String>,
+    /// Saved locations the dashboard switches between, `;` separated in `weer config`
+    pub locations: Option<Vec<String>>,
+    /// Draw condition icons as ascii art
+    pub icons: Option<bool>,
+    /// Minutes a realtime response is cached for
+    pub cache_ttl_current: Option<u64>,
+    /// Minutes a forecast or future response is cached for
+    pub cache_ttl_forecast: Option<u64>,
+    /// Minutes a history response is cached for
+    pub cache_ttl_history: Option<u64>,
+    /// Minutes between two refreshes of `weer watch`
+    pub watch_interval: Option<u64>,
+    /// Most calls per minute
+    pub rate_limit: Option<u32>,
+    /// Calls allowed per billing period
+    pub quota_budget: Option<u64>,
+    /// Day of the month the quota restarts on
+    pub quota_billing_day: Option<u32>,
+    /// Percentage of the budget after which `quota_action` applies
+    pub quota_soft_limit: Option<u8>,
+    pub quota_action: Option<LimitAction>,
+    /// Seconds to connect to the API
+    pub timeout_connect: Option<u64>,
+    /// Seconds to wait for each read of a response
+    pub timeout_read: Option<u64>,
+    /// Proxy url, `HTTPS_PROXY` and the like are used when unset
+    pub proxy: Option<String>,
+    /// Hosts reached without the proxy, `,` separated in `weer config`
+    pub no_proxy: Option<Vec<String>>,
+    /// PEM file of the certificates trusted on top of the usual ones
+    pub root_certificates: Option<PathBuf>
+}
+
+impl Config {
+    /// Language of the responses, detected from the locale when unset
+    pub fn lang(&self) -> Option<Language> {
+        self.lang.or_else(locale_language)
+    }
+
+    pub fn units(&self) -> Units {
+        self.units.unwrap_or_default()
+    }
+
+    pub fn icons(&self) -> bool {
+        self.icons.unwrap_or(true)
+    }
+
+    pub fn cache_ttl_current(&self) -> u64 {
+        self.cache_ttl_current.unwrap_or(10)
+    }
+
+    pub fn cache_ttl_forecast(&self) -> u64 {
+        self.cache_ttl_forecast.unwrap_or(60)
+    }
+
+    pub fn cache_ttl_history(&self) -> u64 {
+        self.cache_ttl_history.unwrap_or(24 * 60)
+    }
+
+    pub fn watch_interval(&self) -> u64 {
+        self.watch_interval.unwrap_or(15)
+    }
+
+    pub fn quota_billing_day(&self) -> u32 {
+        self.quota_billing_day.unwrap_or(1)
+    }
+
+    pub fn quota_soft_limit(&self) -> u8 {
+        self.quota_soft_limit.unwrap_or(90)
+    }
+
+    pub fn quota_action(&self) -> LimitAction {
+        self.quota_action.unwrap_or_default()
+    }
+
+    pub fn timeout_connect(&self) -> u64 {
+        self.timeout_connect.unwrap_or(30)
+    }
+
+    /// Value of `key`, `None` if it isn't set
+    pub fn get(&self, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(match key {
+            "api_key" => self.api_key.clone(),
+            "lang" => self.lang.map(|l| l.to_string()),
+            "units" => self.units.map(|u| u.to_string()),
+            "location" => self.location.clone(),
+            "locations" => self.locations.as_ref().map(|l| l.join("; ")),
+            "icons" => self.icons.map(|i| i.to_string()),
+            "cache_ttl_current" => self.cache_ttl_current.map(|t| t.to_string()),
+            "cache_ttl_forecast" => self.cache_ttl_forecast.map(|t| t.to_string()),
+            "cache_ttl_history" => self.cache_ttl_history.map(|t| t.to_string()),
+            "watch_interval" => self.watch_interval.map(|t| t.to_string()),
+            "rate_limit" => self.rate_limit.map(|r| r.to_string()),
+            "quota_budget" => self.quota_budget.map(|b| b.to_string()),
+            "quota_billing_day" => self.quota_billing_day.map(|d| d.to_string()),
+            "quota_soft_limit" => self.quota_soft_limit.map(|p| p.to_string()),
+            "quota_action" => self.quota_action.map(limit_action_name),
+            "timeout_connect" => self.timeout_connect.map(|t| t.to_string()),
+            "timeout_read" => self.timeout_read.map(|t| t.to_string()),
+            "proxy" => self.proxy.clone(),
+            "no_proxy" => self.no_proxy.as_ref().map(|h| h.join(",")),
+            "root_certificates" => self.root_certificates.as_ref().map(|p| p.display().to_string()),
+            _ => return Err(unknown_key(key))
+        })
+    }
+
+    /// Value of `key` or its default
+    pub fn get_or_default(&self, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(match key {
+            "lang" => self.lang().map(|l| l.to_string()),
+            "units" => Some(self.units().to_string()),
+            "icons" => Some(self.icons().to_string()),
+            "cache_ttl_current" => Some(self.cache_ttl_current().to_string()),
+            "cache_ttl_forecast" => Some(self.cache_ttl_forecast().to_string()),
+            "cache_ttl_history" => Some(self.cache_ttl_history().to_string()),
+            "watch_interval" => Some(self.watch_interval().to_string()),
+            "quota_billing_day" => Some(self.quota_billing_day().to_string()),
+            "quota_soft_limit" => Some(self.quota_soft_limit().to_string()),
+            "quota_action" => Some(limit_action_name(self.quota_action())),
+            "timeout_connect" => Some(self.timeout_connect().to_string()),
+            _ => self.get(key)?
+        })
+    }
+
+    /// Validates and sets `key`
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+        let value = value.trim();
+
+        match key {
+            "api_key" => {
+                if value.is_empty() || !value.chars().all(|c| c.is_ascii_alphanumeric()) {
+                    return Err(text("error-api-key-chars").into());
+                }
+                self.api_key = Some(value.to_string())
+            }
+            "lang" => {
+                let lang = value.parse::<Language>()
+                    .map_err(|_| locale::format("error-unknown-language", &[("lang", &value)]))?;
+                self.lang = Some(lang)
+            }
+            "units" => self.units = Some(value.parse()?),
+            "location" => {
+                if value.is_empty() {
+                    return Err(text("error-empty-location").into());
+                }
+                self.location = Some(value.to_string())
+            }
+            "locations" => {
+                let locations: Vec<String> = value.split(';')
+                    .map(|l| l.trim().to_string())
+                    .collect();
+
+                if locations.iter().any(String::is_empty) {
+                    return Err(text("error-empty-location").into());
+                }
+                self.locations = Some(locations)
+            }
+            "icons" => self.icons = Some(parse_bool(value)?),
+            "cache_ttl_current" => self.cache_ttl_current = Some(parse_minutes(value)?),
+            "cache_ttl_forecast" => self.cache_ttl_forecast = Some(parse_minutes(value)?),
+            "cache_ttl_history" => self.cache_ttl_history = Some(parse_minutes(value)?),
+            "watch_interval" => self.watch_interval = Some(parse_watch_interval(value)?),
+            "rate_limit" => self.rate_limit = Some(parse_calls(value)? as u32),
+            "quota_budget" => self.quota_budget = Some(parse_calls(value)?),
+            "quota_billing_day" => {
+                match value.parse::<u32>() {
+                    Ok(day @ 1..=31) => self.quota_billing_day = Some(day),
+                    _ => return Err(locale::format("error-billing-day", &[("value", &value)]).into())
+                }
+            }
+            "quota_soft_limit" => {
+                match value.trim_end_matches('%').parse::<u8>() {
+                    Ok(percent @ 1..=100) => self.quota_soft_limit = Some(percent),
+                    _ => return Err(locale::format("error-percent", &[("value", &value)]).into())
+                }
+            }
+            "quota_action" => {
+                self.quota_action = Some(match value {
+                    "refuse" => LimitAction::Refuse,
+                    "cache-only" => LimitAction::CacheOnly,
+                    _ => return Err(locale::format("error-quota-action", &[("value", &value)]).into())
+                })
+            }
+            "timeout_connect" => self.timeout_connect = Some(parse_seconds(value)?),
+            "timeout_read" => self.timeout_read = Some(parse_seconds(value)?),
+            "proxy" => {
+                ureq::Proxy::new(value)
+                    .map_err(|err| locale::format("error-proxy", &[("proxy", &value), ("error", &err)]))?;
+                self.proxy = Some(value.to_string())
+            }
+            "no_proxy" => {
+                self.no_proxy = Some(value.split(',')
+                    .map(|h| h.trim().to_string())
+                    .filter(|h| !h.is_empty())
+                    .collect())
+            }
+            "root_certificates" => {
+                let path = fs::canonicalize(value)
+                    .map_err(|err| locale::format("error-root-certificates", &[("path", &value), ("error", &err)]))?;
+                self.root_certificates = Some(path)
+            }
+            _ => return Err(unknown_key(key))
+        }
+
+        Ok(())
+    }
+
+    /// Unsets `key` so that it goes back to its default
+    pub fn unset(&mut self, key: &str) -> Result<(), Box<dyn Error>> {
+        match key {
+            "api_key" => self.api_key = None,
+            "lang" => self.lang = None,
+            "units" => self.units = None,
+            "location" => self.location = None,
+            "locations" => self.locations = None,
+            "icons" => self.icons = None,
+            "cache_ttl_current" => self.cache_ttl_current = None,
+            "cache_ttl_forecast" => self.cache_ttl_forecast = None,
+            "cache_ttl_history" => self.cache_ttl_history = None,
+            "watch_interval" => self.watch_interval = None,
+            "rate_limit" => self.rate_limit = None,
+            "quota_budget" => self.quota_budget = None,
+            "quota_billing_day" => self.quota_billing_day = None,
+            "quota_soft_limit" => self.quota_soft_limit = None,
+            "quota_action" => self.quota_action = None,
+            "timeout_connect" => self.timeout_connect = None,
+            "timeout_read" => self.timeout_read = None,
+            "proxy" => self.proxy = None,
+            "no_proxy" => self.no_proxy = None,
+            "root_certificates" => self.root_certificates = None,
+            _ => return Err(unknown_key(key))
+        }
+
+        Ok(())
+    }
+
+    /// Client with the configured timeouts, proxy and root certificates
+    pub fn client(&self, api_key: &str) -> Result<Client, Box<dyn Error>> {
+        let mut builder = Client::builder(api_key);
+        builder.timeout_connect(Duration::from_secs(self.timeout_connect()));
+
+        if let Some(seconds) = self.timeout_read {
+            builder.timeout_read(Duration::from_secs(seconds));
+        }
+        if let Some(proxy) = &self.proxy {
+            builder.proxy(proxy);
+        }
+        if let Some(hosts) = &self.no_proxy {
+            builder.no_proxy(&hosts.iter().map(String::as_str).collect::<Vec<_>>());
+        }
+        if let Some(path) = &self.root_certificates {
+            let pem = fs::read(path)
+                .map_err(|err| locale::format("error-root-certificates", &[("path", &path.display()), ("error", &err)]))?;
+            builder.root_certificates(&pem);
+        }
+
+        builder.build()
+            .map_err(|err| locale::format("error-client", &[("error", &err)]).into())
+    }
+
+    /// Response cache following the configured time to live of each endpoint
+    pub fn cache(&self) -> Option<Cache> {
+        let dirs = ProjectDirs::from("rs", "", APP_NAME)?;
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+
+        let mut cache = Cache::new(dirs.cache_dir());
+        cache.ttl("current", minutes(self.cache_ttl_current()))
+            .ttl("forecast", minutes(self.cache_ttl_forecast()))
+            .ttl("future", minutes(self.cache_ttl_forecast()))
+            .ttl("history", minutes(self.cache_ttl_history()));
+
+        Some(cache)
+    }
+
+    /// Call counter of the api keys, with the configured budget
+    pub fn quota(&self) -> Option<Quota> {
+        let mut quota = Quota::new(data_dir()?.join("quota.json"));
+        quota.billing_day(self.quota_billing_day())
+            .soft_limit(self.quota_soft_limit())
+            .action(self.quota_action());
+
+        if let Some(budget) = self.quota_budget {
+            quota.budget(budget);
+        }
+
+        Some(quota)
+    }
+
+    pub fn rate_limiter(&self) -> Option<RateLimiter> {
+        self.rate_limit.map(|calls| RateLimiter::new(calls, Duration::from_secs(60)))
+    }
+
+    /// Settings of `self`, falling back to `parent` for the unset ones
+    pub fn inherit(&self, parent: &Config) -> Config {
+        Config {
+            lang: self.lang.or(parent.lang),
+            api_key: self.api_key.clone().or_else(|| parent.api_key.clone()),
+            units: self.units.or(parent.units),
+            location: self.location.clone().or_else(|| parent.location.clone()),
+            locations: self.locations.clone().or_else(|| parent.locations.clone()),
+            icons: self.icons.or(parent.icons),
+            cache_ttl_current: self.cache_ttl_current.or(parent.cache_ttl_current),
+            cache_ttl_forecast: self.cache_ttl_forecast.or(parent.cache_ttl_forecast),
+            cache_ttl_history: self.cache_ttl_history.or(parent.cache_ttl_history),
+            watch_interval: self.watch_interval.or(parent.watch_interval),
+            rate_limit: self.rate_limit.or(parent.rate_limit),
+            quota_budget: self.quota_budget.or(parent.quota_budget),
+            quota_billing_day: self.quota_billing_day.or(parent.quota_billing_day),
+            quota_soft_limit: self.quota_soft_limit.or(parent.quota_soft_limit),
+            quota_action: self.quota_action.or(parent.quota_action),
+            timeout_connect: self.timeout_connect.or(parent.timeout_connect),
+            timeout_read: self.timeout_read.or(parent.timeout_read),
+            proxy: self.proxy.clone().or_else(|| parent.proxy.clone()),
+            no_proxy: self.no_proxy.clone().or_else(|| parent.no_proxy.clone()),
+            root_certificates: self.root_certificates.clone().or_else(|| parent.root_certificates.clone())
+        }
+    }
+}
+
+
+/// Contents of the config file
+///
+/// The top level settings are the default profile, named profiles inherit every setting they don't set from it.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ConfigFile {
+    #[serde(flatten)]
+    pub default: Config,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Config>
+}
+
+impl ConfigFile {
+    pub fn load() -> Result<Self, Box<dyn Error>> {
+        Ok(confy::load(APP_NAME, CONFIG_NAME)?)
+    }
+
+    /// Writes the config file, readable only by the current user
+    pub fn store(&self) -> Result<(), Box<dyn Error>> {
+        let path = Self::path()?;
+        create_private(&path)?;
+        confy::store_path(&path, self)?;
+        Ok(())
+    }
+
+    pub fn path() -> Result<PathBuf, Box<dyn Error>> {
+        Ok(confy::get_configuration_file_path(APP_NAME, CONFIG_NAME)?)
+    }
+
+    /// Effective settings of a profile
+    pub fn profile(&self, name: &str) -> Result<Config, Box<dyn Error>> {
+        if name == DEFAULT_PROFILE {
+            return Ok(self.default.clone());
+        }
+
+        self.profiles.get(name)
+            .map(|profile| profile.inherit(&self.default))
+            .ok_or_else(|| unknown_profile(name))
+    }
+
+    /// Settings set in a profile itself
+    pub fn profile_mut(&mut self, name: &str) -> Result<&mut Config, Box<dyn Error>> {
+        if name == DEFAULT_PROFILE {
+            return Ok(&mut self.default);
+        }
+
+        self.profiles.get_mut(name).ok_or_else(|| unknown_profile(name))
+    }
+
+    /// Names of every profile, starting with the default one
+    pub fn profile_names(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(DEFAULT_PROFILE).chain(self.profiles.keys().map(|k| k.as_str()))
+    }
+
+    /// Creates an empty profile that inherits everything from the default one
+    pub fn create_profile(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        self.insert_profile(name, Config::default())
+    }
+
+    /// Creates `dst` with the settings set in `src`
+    pub fn copy_profile(&mut self, src: &str, dst: &str) -> Result<(), Box<dyn Error>> {
+        let profile = self.profile_mut(src)?.clone();
+        self.insert_profile(dst, profile)
+    }
+
+    pub fn delete_profile(&mut self, name: &str) -> Result<(), Box<dyn Error>> {
+        if name == DEFAULT_PROFILE {
+            return Err(text("error-delete-default-profile").into());
+        }
+
+        self.profiles.remove(name).map(|_| ()).ok_or_else(|| unknown_profile(name))
+    }
+
+    fn insert_profile(&mut self, name: &str, profile: Config) -> Result<(), Box<dyn Error>> {
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(locale::format("error-profile-name", &[("name", &name)]).into());
+        }
+
+        if name == DEFAULT_PROFILE || self.profiles.contains_key(name) {
+            return Err(locale::format("error-profile-exists", &[("name", &name)]).into());
+        }
+
+        self.profiles.insert(name.to_string(), profile);
+        Ok(())
+    }
+}
+
+/// Directory of the state weer keeps between runs
+pub fn data_dir() -> Option<PathBuf> {
+    ProjectDirs::from("rs", "", APP_NAME).map(|dirs| dirs.data_dir().to_path_buf())
+}
+
+/// Name of the selected profile
+///
+/// `--profile` takes precedence over the `WEER_PROFILE` environment variable.
+pub fn selected_profile(flag: Option<&String>) -> String {
+    flag.cloned()
+        .or_else(|| env::var(PROFILE_ENV).ok().filter(|p| !p.trim().is_empty()))
+        .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+}
+
+/// Resolves the api key
+///
+/// The key is looked up in the `WEER_API_KEY` environment variable, then in `key_file` and finally in the config file.
+pub fn resolve_api_key(cfg: &Config, key_file: Option<&Path>) -> Result<String, Box<dyn Error>> {
+    if let Some(key) = env::var(API_KEY_ENV).ok().filter(|k| !k.trim().is_empty()) {
+        return Ok(key.trim().to_string());
+    }
+
+    if let Some(path) = key_file {
+        let key = fs::read_to_string(path)
+            .map_err(|e| locale::format("error-api-key-file", &[("path", &path.display()), ("error", &e)]))?;
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(locale::format("error-api-key-file-empty", &[("path", &path.display())]).into());
+        }
+        return Ok(key.to_string());
+    }
+
+    match &cfg.api_key {
+        Some(key) if !key.trim().is_empty() => Ok(key.trim().to_string()),
+        _ => Err(locale::format("error-no-api-key", &[("env", &API_KEY_ENV)]).into())
+    }
+}
+
+/// Language of the locale set in `LC_ALL`, `LC_MESSAGES` or `LANG`
+fn locale_language() -> Option<Language> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|locale| !locale.is_empty())
+        .and_then(|locale| Language::from_locale(&locale))
+}
+
+fn unknown_key(key: &str) -> Box<dyn Error> {
+    locale::format("error-unknown-key", &[("key", &key), ("keys", &KEYS.join(", "))]).into()
+}
+
+fn unknown_profile(name: &str) -> Box<dyn Error> {
+    locale::format("error-unknown-profile", &[("name", &name)]).into()
+}
+
+fn parse_bool(value: &str) -> Result<bool, Box<dyn Error>> {
+    match value.to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        _ => Err(locale::format("error-expected-bool", &[("value", &value)]).into())
+    }
+}
+
+fn parse_minutes(value: &str) -> Result<u64, Box<dyn Error>> {
+    value.parse()
+        .map_err(|_| locale::format("error-expected-minutes", &[("value", &value)]).into())
+}
+
+fn parse_seconds(value: &str) -> Result<u64, Box<dyn Error>> {
+    match value.trim_end_matches('s').parse() {
+        Ok(seconds) if seconds > 0 => Ok(seconds),
+        _ => Err(locale::format("error-expected-seconds", &[("value", &value)]).into())
+    }
+}
+
+fn parse_calls(value: &str) -> Result<u64, Box<dyn Error>> {
+    match value.parse() {
+        Ok(calls) if calls > 0 && calls <= u32::MAX as u64 => Ok(calls),
+        _ => Err(locale::format("error-expected-calls", &[("value", &value)]).into())
+    }
+}
+
+/// Name of an action as set with `weer config`
+pub fn limit_action_name(action: LimitAction) -> String {
+    match action {
+        LimitAction::Refuse => "refuse",
+        LimitAction::CacheOnly => "cache-only"
+    }.to_string()
+}
+
+pub fn parse_watch_interval(value: &str) -> Result<u64, Box<dyn Error>> {
+    match parse_minutes(value)? {
+        minutes if minutes < MIN_WATCH_INTERVAL => {
+            Err(locale::format("error-watch-interval", &[("min", &MIN_WATCH_INTERVAL)]).into())
+        }
+        minutes => Ok(minutes)
+    }
+}
+
+/// Creates `path` if needed and makes it private to the current user
+///
+/// confy truncates the file in place, so the mode survives the following write.
+fn create_private(path: &Path) -> Result<(), Box<dyn Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(false);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        options.mode(0o600);
+        options.open(path)?;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    #[cfg(not(unix))]
+    options.open(path)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_get_unset() {
+        let mut cfg = Config::default();
+
+        for (key, value, stored) in [
+            ("api_key", " abcdef123 ", "abcdef123"),
+            ("lang", "German", "de"),
+            ("units", "F", "imperial"),
+            ("locations", "Paris ; Lyon", "Paris; Lyon"),
+            ("icons", "off", "false"),
+            ("watch_interval", "30", "30"),
+            ("quota_soft_limit", "80%", "80"),
+            ("quota_action", "cache-only", "cache-only"),
+            ("timeout_read", "10s", "10"),
+            ("no_proxy", "localhost, ,example.com", "localhost,example.com")
+        ] {
+            cfg.set(key, value).unwrap();
+            assert_eq!(Some(stored.to_string()), cfg.get(key).unwrap(), "{}", key);
+
+            // What `get` shows is accepted back
+            let mut copy = Config::default();
+            copy.set(key, stored).unwrap();
+            assert_eq!(cfg.get(key).unwrap(), copy.get(key).unwrap(), "{}", key);
+
+            cfg.unset(key).unwrap();
+            assert_eq!(None, cfg.get(key).unwrap(), "{}", key);
+        }
+
+        cfg.set("units", "imperial").unwrap();
+        cfg.unset("units").unwrap();
+        assert_eq!(Some("metric".to_string()), cfg.get_or_default("units").unwrap());
+        assert_eq!(Some("15".to_string()), cfg.get_or_default("watch_interval").unwrap());
+
+        assert!(cfg.get("colour").is_err());
+        assert!(cfg.unset("colour").is_err());
+    }
+
+    #[test]
+    fn invalid_values() {
+        let mut cfg = Config::default();
+
+        for (key, value) in [
+            ("api_key", "abc-123"),
+            ("api_key", ""),
+            ("lang", "klingon"),
+            ("units", "kelvin"),
+            ("location", " "),
+            ("locations", "Paris;;Lyon"),
+            ("icons", "maybe"),
+            ("cache_ttl_current", "-1"),
+            ("watch_interval", "1"),
+            ("rate_limit", "0"),
+            ("quota_budget", "lots"),
+            ("quota_billing_day", "32"),
+            ("quota_soft_limit", "0%"),
+            ("quota_soft_limit", "101"),
+            ("quota_action", "ignore"),
+            ("timeout_connect", "0"),
+            ("proxy", "ftp://proxy.example.com:21"),
+            ("root_certificates", "/nonexistent/weer.pem"),
+            ("colour", "blue")
+        ] {
+            assert!(cfg.set(key, value).is_err(), "{} = {:?} was accepted", key, value);
+        }
+
+        // Nothing was set on the way
+        assert!(KEYS.iter().all(|key| cfg.get(key).unwrap().is_none()));
+    }
+
+    #[test]
+    fn profiles() {
+        let mut file = ConfigFile::default();
+        file.default.set("units", "imperial").unwrap();
+        file.default.set("location", "London").unwrap();
+        file.default.set("watch_interval", "30").unwrap();
+
+        file.create_profile("work").unwrap();
+        let work = file.profile_mut("work").unwrap();
+        work.set("location", "Paris").unwrap();
+        work.set("icons", "false").unwrap();
+
+        // Unset keys come from the default profile, set ones override it
+        let cfg = file.profile("work").unwrap();
+        assert_eq!(Units::Imperial, cfg.units());
+        assert_eq!(Some(30), cfg.watch_interval);
+        assert_eq!(Some("Paris"), cfg.location.as_deref());
+        assert!(!cfg.icons());
+        assert_eq!(None, cfg.api_key);
+
+        // Only the profile itself keeps what it sets
+        assert_eq!(None, file.profile_mut("work").unwrap().units);
+        assert_eq!(Some("London"), file.profile(DEFAULT_PROFILE).unwrap().location.as_deref());
+        assert_eq!(None, file.profile(DEFAULT_PROFILE).unwrap().icons);
+
+        assert!(file.profile("home").is_err());
+        assert!(file.profile_mut("home").is_err());
+        assert!(file.delete_profile(DEFAULT_PROFILE).is_err());
+        assert!(file.create_profile("work").is_err());
+    }
+
+    #[test]
+    fn profile_selection() {
+        // The only test that touches the variable
+        env::set_var(PROFILE_ENV, "work");
+        assert_eq!("work", selected_profile(None));
+        assert_eq!("home", selected_profile(Some(&"home".to_string())));
+
+        env::set_var(PROFILE_ENV, " ");
+        assert_eq!(DEFAULT_PROFILE, selected_profile(None));
+
+        env::remove_var(PROFILE_ENV);
+        assert_eq!(DEFAULT_PROFILE, selected_profile(None));
+        assert_eq!("home", selected_profile(Some(&"home".to_string())));
+    }
+}
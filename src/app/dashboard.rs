@@ -0,0 +1,529 @@
+//! Full-screen dashboard of `weer dashboard`
+//!
+//! Every saved location gets a tab with the current conditions, air quality, alerts, hourly charts of the selected
+//! day and the multi-day forecast. Forecasts are fetched when a tab is first shown and then every `watch_interval`, a
+//! failed refresh keeps the last forecast on screen, marked as stale.
+
+use std::{
+    error::Error,
+    time::{Duration, Instant}
+};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::{
+    DefaultTerminal, Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    symbols,
+    text::Line,
+    widgets::{
+        Axis, Bar, BarChart, BarGroup, Block, Chart, Dataset, GraphType, Paragraph, Row, Table, TableState, Tabs, Wrap
+    }
+};
+use weer_api::{chrono::{DateTime, Local, Utc}, BaseApi, Client, Forecast, ForecastDay, Language, Query};
+use super::{config::Units, handler, tables, locale::text};
+
+
+/// Forecast of a location and when it was fetched
+struct Loaded {
+    forecast: Result<Forecast, String>,
+    /// Last request, failed or not, so that a failing location waits for the next refresh too
+    at: Instant,
+    /// When the forecast was fetched
+    fetched: DateTime<Local>,
+    /// Error of the last refresh, the forecast on screen is older
+    stale: Option<String>
+}
+
+pub struct Dashboard<'a> {
+    client: &'a Client,
+    lang: Option<Language>,
+    days: u8,
+    refresh: Duration,
+    locations: Vec<Query>,
+    loaded: Vec<Option<Loaded>>,
+    /// Selected location
+    tab: usize,
+    /// Selected day of the forecast
+    day: TableState,
+    units: Units
+}
+
+impl<'a> Dashboard<'a> {
+    pub fn new(client: &'a Client, locations: Vec<Query>) -> Self {
+        Self {
+            client,
+            lang: None,
+            days: 3,
+            refresh: Duration::from_secs(15 * 60),
+            loaded: locations.iter().map(|_| None).collect(),
+            locations,
+            tab: 0,
+            day: TableState::default().with_selected(0),
+            units: Units::default()
+        }
+    }
+
+    pub fn lang(&mut self, lang: Option<Language>) -> &mut Self {
+        self.lang = lang;
+        self
+    }
+
+    pub fn days(&mut self, days: u8) -> &mut Self {
+        self.days = days;
+        self
+    }
+
+    /// Age after which the forecast of the selected location is fetched again
+    pub fn refresh(&mut self, refresh: Duration) -> &mut Self {
+        self.refresh = refresh;
+        self
+    }
+
+    pub fn units(&mut self, units: Units) -> &mut Self {
+        self.units = units;
+        self
+    }
+
+    /// Shows the dashboard until the user quits
+    pub fn run(&mut self) -> Result<(), Box<dyn Error>> {
+        let mut terminal = ratatui::try_init()?;
+        let result = self.event_loop(&mut terminal);
+        ratatui::try_restore()?;
+        result
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<(), Box<dyn Error>> {
+        loop {
+            if self.stale() {
+                // Requests block, so say what is going on first
+                terminal.draw(|frame| self.draw(frame))?;
+                self.fetch();
+            }
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if !event::poll(Duration::from_secs(1))? {
+                continue;
+            }
+
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press && !self.handle_key(key) => return Ok(()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Applies a key press, `false` when it quits
+    fn handle_key(&mut self, key: KeyEvent) -> bool {
+        let count = self.locations.len();
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => self.select((self.tab + 1) % count),
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => self.select((self.tab + count - 1) % count),
+            KeyCode::Char(c @ '1'..='9') => {
+                let tab = c as usize - '1' as usize;
+                if tab < count {
+                    self.select(tab);
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.forecast_days().map_or(0, |days| days.len().saturating_sub(1));
+                let day = self.day.selected().unwrap_or(0);
+                self.day.select(Some((day + 1).min(last)));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                let day = self.day.selected().unwrap_or(0);
+                self.day.select(Some(day.saturating_sub(1)));
+            }
+            KeyCode::Char('u') => {
+                self.units = match self.units {
+                    Units::Metric => Units::Imperial,
+                    Units::Imperial => Units::Metric
+                }
+            }
+            KeyCode::Char('r') => self.loaded[self.tab] = None,
+            _ => {}
+        }
+
+        true
+    }
+
+    fn select(&mut self, tab: usize) {
+        self.tab = tab;
+        self.day.select(Some(0));
+    }
+
+    /// Whether the selected location has to be fetched
+    fn stale(&self) -> bool {
+        self.loaded[self.tab].as_ref().is_none_or(|loaded| loaded.at.elapsed() >= self.refresh)
+    }
+
+    fn fetch(&mut self) {
+        let mut req = self.client.forecast();
+        req.query(self.locations[self.tab].clone())
+            .dt(Utc::now())
+            .days(self.days)
+            .aqi(true)
+            .alerts(true);
+
+        if let Some(lang) = self.lang {
+            req.lang(lang);
+        }
+
+        let forecast = req.call().map_err(handler::describe_error);
+        let at = Instant::now();
+
+        self.loaded[self.tab] = Some(match (forecast, self.loaded[self.tab].take()) {
+            // A failed refresh keeps showing the last forecast, marked as stale
+            (Err(err), Some(last @ Loaded { forecast: Ok(_), .. })) => Loaded { at, stale: Some(err), ..last },
+            (forecast, _) => Loaded { forecast, at, fetched: Local::now(), stale: None }
+        });
+    }
+
+    fn forecast(&self) -> Option<&Forecast> {
+        self.loaded[self.tab].as_ref().and_then(|loaded| loaded.forecast.as_ref().ok())
+    }
+
+    fn forecast_days(&self) -> Option<&[ForecastDay]> {
+        self.forecast().map(|f| f.forecast.forecast_day.as_slice())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [tabs, body, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1)
+        ]).areas(frame.area());
+
+        self.draw_tabs(frame, tabs);
+        frame.render_widget(Line::from(text("dashboard-help")).dark_gray(), help);
+
+        let error = match self.loaded[self.tab].as_ref().map(|loaded| &loaded.forecast) {
+            None => Some(text("loading").to_string()),
+            Some(Err(err)) => Some(format!("{}: {}", text("error"), err)),
+            Some(Ok(_)) => None
+        };
+
+        if let Some(message) = error {
+            frame.render_widget(Paragraph::new(message).block(Block::bordered()).wrap(Wrap { trim: true }), body);
+            return;
+        }
+
+        let days = self.forecast_days().map_or(0, |days| days.len()) as u16;
+        let [top, charts, table] = Layout::vertical([
+            Constraint::Length(8),
+            Constraint::Min(8),
+            Constraint::Length(days + 3)
+        ]).areas(body);
+
+        let [current, air, alerts] = Layout::horizontal([
+            Constraint::Percentage(35),
+            Constraint::Percentage(25),
+            Constraint::Percentage(40)
+        ]).areas(top);
+
+        let [temperature, precipitation] = Layout::horizontal([
+            Constraint::Percentage(50),
+            Constraint::Percentage(50)
+        ]).areas(charts);
+
+        self.draw_current(frame, current);
+        self.draw_air_quality(frame, air);
+        self.draw_alerts(frame, alerts);
+        self.draw_temperature(frame, temperature);
+        self.draw_precipitation(frame, precipitation);
+        self.draw_days(frame, table);
+    }
+
+    fn draw_tabs(&self, frame: &mut Frame, area: Rect) {
+        let titles = self.locations.iter().zip(&self.loaded).map(|(q, loaded)| {
+            match loaded.as_ref().map(|loaded| &loaded.forecast) {
+                Some(Ok(f)) => f.location.name.clone(),
+                _ => q.to_string()
+            }
+        });
+
+        let mut block = Block::bordered().title_bottom(Line::from(self.units.to_string()).right_aligned());
+        if let Some(Loaded { fetched, stale: Some(err), .. }) = &self.loaded[self.tab] {
+            let stale = format!("{} {} ({})", text("stale"), fetched.format("%H:%M"), err);
+            block = block.title_bottom(Line::from(stale).yellow().bold().left_aligned());
+        }
+
+        let tabs = Tabs::new(titles)
+            .select(self.tab)
+            .highlight_style(Style::new().bold().fg(Color::Yellow))
+            .block(block);
+
+        frame.render_widget(tabs, area);
+    }
+
+    fn draw_current(&self, frame: &mut Frame, area: Rect) {
+        let Some(f) = self.forecast() else { return };
+        let current = &f.current;
+
+        let lines = vec![
+            Line::from(current.condition.text.clone()).bold(),
+            Line::from(format!(
+                "{}: {} ({} {})",
+                text("temperature"),
+                tables::temperature(current.temp_c, current.temp_f, self.units),
+                text("feels-like"),
+                tables::temperature(current.feelslike_c, current.feelslike_f, self.units)
+            )),
+            Line::from(format!(
+                "{}: {}",
+                text("wind"),
+                tables::wind(current.wind_kph, current.wind_mph, &current.wind_dir, self.units)
+            )),
+            Line::from(format!(
+                "{}: {}",
+                text("precipitation"),
+                tables::precipitation(current.precip_mm, current.precip_in, self.units)
+            )),
+            Line::from(format!("{}: {}", text("humidity"), current.humidity)),
+            Line::from(format!("{}: {}", text("last-updated"), current.last_updated)).dark_gray()
+        ];
+
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(text("current"))), area);
+    }
+
+    fn draw_air_quality(&self, frame: &mut Frame, area: Rect) {
+        let Some(f) = self.forecast() else { return };
+
+        let lines = match &f.current.air_quality {
+            Some(air) => vec![
+                Line::from(format!("{} ({})", air.us_epa_index, tables::epa_level(air.us_epa_index))).bold(),
+                Line::from(format!("PM2.5: {:.1}", air.pm2_5)),
+                Line::from(format!("PM10: {:.1}", air.pm10)),
+                Line::from(format!("O3: {:.1}", air.ozone)),
+                Line::from(format!("NO2: {:.1}", air.nitrogen_dioxide))
+            ],
+            None => vec![]
+        };
+
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(text("air-quality"))), area);
+    }
+
+    fn draw_alerts(&self, frame: &mut Frame, area: Rect) {
+        let Some(f) = self.forecast() else { return };
+        let alerts = f.alerts.as_ref().map_or(&[][..], |a| a.alert.as_slice());
+
+        let lines: Vec<Line> = match alerts {
+            [] => vec![Line::from(text("no-alerts")).dark_gray()],
+            alerts => alerts.iter()
+                .map(|alert| Line::from(format!("{}: {}", alert.severity, alert.head_line)).red())
+                .collect()
+        };
+
+        let block = Block::bordered().title(text("alerts"));
+        frame.render_widget(Paragraph::new(lines).block(block).wrap(Wrap { trim: true }), area);
+    }
+
+    fn selected_day(&self) -> Option<&ForecastDay> {
+        self.forecast_days()?.get(self.day.selected().unwrap_or(0))
+    }
+
+    fn draw_temperature(&self, frame: &mut Frame, area: Rect) {
+        let Some(fd) = self.selected_day() else { return };
+
+        let points: Vec<(f64, f64)> = fd.hour.iter()
+            .enumerate()
+            .map(|(i, h)| (i as f64, match self.units {
+                Units::Metric => h.temp_c,
+                Units::Imperial => h.temp_f
+            } as f64))
+            .collect();
+
+        let min = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min).floor() - 1.0;
+        let max = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max).ceil() + 1.0;
+        let unit = match self.units {
+            Units::Metric => "°C",
+            Units::Imperial => "°F"
+        };
+
+        let dataset = Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .yellow()
+            .data(&points);
+
+        let chart = Chart::new(vec![dataset])
+            .block(Block::bordered().title(format!("{} - {}", text("hourly-temperature"), fd.date)))
+            .x_axis(Axis::default()
+                .bounds([0.0, 23.0])
+                .labels(["00:00", "12:00", "23:00"]))
+            .y_axis(Axis::default()
+                .bounds([min, max])
+                .labels([format!("{}{}", min, unit), format!("{}{}", max, unit)]));
+
+        frame.render_widget(chart, area);
+    }
+
+    fn draw_precipitation(&self, frame: &mut Frame, area: Rect) {
+        let Some(fd) = self.selected_day() else { return };
+
+        let width = (area.width.saturating_sub(2) / 24).saturating_sub(1).max(1);
+
+        // Bars hold integers, so amounts are kept in hundredths
+        let bars: Vec<Bar> = fd.hour.iter()
+            .enumerate()
+            .map(|(i, h)| {
+                let amount = match self.units {
+                    Units::Metric => h.precip_mm,
+                    Units::Imperial => h.precip_in
+                };
+
+                // Labels wider than the bars would be cut to their first digit
+                let label = if width >= 2 && i % 3 == 0 { format!("{:02}", i) } else { String::new() };
+
+                Bar::default()
+                    .value((amount * 100.0).round() as u64)
+                    .text_value(String::new())
+                    .label(Line::from(label))
+            })
+            .collect();
+
+        let total = tables::precipitation(fd.day.totalprecip_mm, fd.day.totalprecip_in, self.units);
+
+        let chart = BarChart::default()
+            .block(Block::bordered().title(format!("{} - {}", text("hourly-precipitation"), total)))
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(width)
+            .bar_gap(1)
+            .bar_style(Style::new().blue());
+
+        frame.render_widget(chart, area);
+    }
+
+    fn draw_days(&mut self, frame: &mut Frame, area: Rect) {
+        let units = self.units;
+        let Some(days) = self.forecast_days() else { return };
+
+        let rows: Vec<Row> = days.iter()
+            .map(|fd| Row::new([
+                fd.date.clone(),
+                fd.day.condition.text.clone(),
+                format!(
+                    "{} / {}",
+                    tables::temperature(fd.day.temp_c().min(), fd.day.temp_f().min(), units),
+                    tables::temperature(fd.day.temp_c().max(), fd.day.temp_f().max(), units)
+                ),
+                fd.day.daily_chance_of_rain.map(|c| format!("{}%", c)).unwrap_or_default(),
+                tables::wind(fd.day.maxwind_kph, fd.day.maxwind_mph, "", units)
+            ]))
+            .collect();
+
+        let header = Row::new([text("date"), text("condition"), text("temperature"), text("rain-chance"), text("wind")])
+            .add_modifier(Modifier::BOLD);
+
+        let table = Table::new(rows, [
+            Constraint::Length(10),
+            Constraint::Fill(2),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Fill(1)
+        ])
+            .header(header)
+            .row_highlight_style(Style::new().reversed())
+            .block(Block::bordered().title(text("forecast")));
+
+        frame.render_stateful_widget(table, area, &mut self.day);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{Terminal, backend::TestBackend};
+    use weer_api::mock::{self, MockServer};
+
+    fn press(dashboard: &mut Dashboard, code: KeyCode) -> bool {
+        dashboard.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    fn screen(dashboard: &mut Dashboard) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|frame| dashboard.draw(frame)).unwrap();
+        terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn keys() {
+        let server = MockServer::start().unwrap();
+        let client = server.client("api_key");
+        let cities = ["London", "Paris", "Madrid"].map(|c| Query::City(c.to_string()));
+        let mut dashboard = Dashboard::new(&client, cities.to_vec());
+
+        assert!(press(&mut dashboard, KeyCode::Tab));
+        assert_eq!(1, dashboard.tab);
+        press(&mut dashboard, KeyCode::Char('l'));
+        press(&mut dashboard, KeyCode::Right);
+        assert_eq!(0, dashboard.tab);
+        press(&mut dashboard, KeyCode::Left);
+        assert_eq!(2, dashboard.tab);
+        press(&mut dashboard, KeyCode::BackTab);
+        assert_eq!(1, dashboard.tab);
+        press(&mut dashboard, KeyCode::Char('1'));
+        assert_eq!(0, dashboard.tab);
+        press(&mut dashboard, KeyCode::Char('9'));
+        assert_eq!(0, dashboard.tab);
+
+        dashboard.fetch();
+        press(&mut dashboard, KeyCode::Down);
+        press(&mut dashboard, KeyCode::Down);
+        assert_eq!(Some(1), dashboard.day.selected());
+        press(&mut dashboard, KeyCode::Char('k'));
+        assert_eq!(Some(0), dashboard.day.selected());
+
+        press(&mut dashboard, KeyCode::Char('u'));
+        assert_eq!(Units::Imperial, dashboard.units);
+
+        assert!(!dashboard.stale());
+        press(&mut dashboard, KeyCode::Char('r'));
+        assert!(dashboard.stale());
+
+        assert!(!press(&mut dashboard, KeyCode::Char('q')));
+        assert!(!press(&mut dashboard, KeyCode::Esc));
+        assert!(!dashboard.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+        assert!(press(&mut dashboard, KeyCode::Char('c')));
+    }
+
+    #[test]
+    fn refresh() {
+        let server = MockServer::start().unwrap();
+        let client = server.client("api_key");
+        let mut dashboard = Dashboard::new(&client, vec![Query::City("London".to_string())]);
+        assert!(dashboard.stale());
+
+        dashboard.fetch();
+        assert_eq!("London", dashboard.forecast().unwrap().location.name);
+        assert!(!dashboard.stale());
+        assert_eq!(1, server.requests().len());
+
+        dashboard.refresh(Duration::ZERO);
+        assert!(dashboard.stale());
+
+        // A failed refresh keeps the last forecast and waits for the next one
+        dashboard.locations[0] = Query::City(mock::UNKNOWN_LOCATION.to_string());
+        dashboard.fetch();
+        let loaded = dashboard.loaded[0].as_ref().unwrap();
+        assert_eq!(Some("No matching location found."), loaded.stale.as_deref());
+        assert_eq!("London", dashboard.forecast().unwrap().location.name);
+
+        dashboard.refresh(Duration::from_secs(60));
+        assert!(!dashboard.stale());
+        assert!(screen(&mut dashboard).contains("Stale, last updated at"));
+
+        // Without a forecast the error is shown instead
+        dashboard.loaded[0] = None;
+        dashboard.fetch();
+        let loaded = dashboard.loaded[0].as_ref().unwrap();
+        assert_eq!(Some("No matching location found."), loaded.forecast.as_ref().err().map(String::as_str));
+        assert!(!dashboard.stale());
+        assert!(screen(&mut dashboard).contains("No matching location found."));
+    }
+}
@@ -0,0 +1,235 @@
+//! Error classes of the CLI and their sysexits exit codes
+//!
+//! Most errors are plain messages, the ones whose class can't be told from their type are wrapped in an [`AppError`]
+//! where they are raised. Errors of the API are classified from the status and the error code of the response.
+
+use std::{
+    any::Any,
+    error::Error,
+    fmt::{self, Display},
+    io, panic,
+    sync::Mutex
+};
+
+use weer_api::QuotaExceeded;
+use super::locale;
+
+
+/// Last panic, as reported by the panic hook
+static PANIC: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    /// Wrong command line
+    Usage,
+    /// The API doesn't know the location
+    Location,
+    /// Malformed response, cassette or file
+    Data,
+    /// Invalid or disabled api key, or exceeded quota
+    Auth,
+    /// The API can't be reached
+    Network,
+    /// Unreadable or invalid config, or no api key at all
+    Config,
+    /// A file or the output can't be read or written
+    Io,
+    /// Bug in weer
+    Internal
+}
+
+impl Kind {
+    pub fn exit_code(&self) -> exitcode::ExitCode {
+        match self {
+            Kind::Usage => exitcode::USAGE,
+            Kind::Location => exitcode::DATAERR,
+            Kind::Data => exitcode::DATAERR,
+            Kind::Auth => exitcode::NOPERM,
+            Kind::Network => exitcode::UNAVAILABLE,
+            Kind::Config => exitcode::CONFIG,
+            Kind::Io => exitcode::IOERR,
+            Kind::Internal => exitcode::SOFTWARE
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AppError {
+    pub kind: Kind,
+    message: String
+}
+
+impl Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for AppError {}
+
+pub fn new(kind: Kind, err: impl Display) -> Box<dyn Error> {
+    Box::new(AppError { kind, message: err.to_string() })
+}
+
+/// Marks an error as a config error, unless it already has a class
+pub fn config(err: Box<dyn Error>) -> Box<dyn Error> {
+    if err.is::<AppError>() || err.is::<ureq::Error>() {
+        return err;
+    }
+
+    new(Kind::Config, err)
+}
+
+/// Marks an I/O error as such, or as a data error when what it read is malformed, unless it already has a class
+pub fn io(err: Box<dyn Error>) -> Box<dyn Error> {
+    match err.downcast::<io::Error>() {
+        Ok(err) if err.kind() == io::ErrorKind::InvalidData => new(Kind::Data, err),
+        Ok(err) => new(Kind::Io, err),
+        Err(err) => err
+    }
+}
+
+/// Code and message of an error returned by Weather API
+pub fn api_error(resp: ureq::Response) -> (Option<u64>, String) {
+    let body: Option<serde_json::Value> = resp.into_json().ok();
+    let error = body.as_ref().map(|b| &b["error"]);
+
+    (
+        error.and_then(|e| e["code"].as_u64()),
+        error.and_then(|e| e["message"].as_str()).unwrap_or("unknown error").to_string()
+    )
+}
+
+/// Class of a failed request, its message never holds the url as it has the api key
+fn request_error(err: ureq::Error) -> AppError {
+    match err {
+        ureq::Error::Status(status, resp) => {
+            let (code, message) = api_error(resp);
+            // https://www.weatherapi.com/docs/#intro-error-codes
+            let kind = match (status, code) {
+                (_, Some(1003 | 1005 | 1006)) => Kind::Location,
+                (_, Some(1002 | 2006 | 2007 | 2008 | 2009)) => Kind::Auth,
+                (401 | 403 | 429, _) => Kind::Auth,
+                (500.., _) => Kind::Network,
+                _ => Kind::Usage
+            };
+
+            AppError { kind, message }
+        }
+        // Refused before being sent, the key is as good as rejected
+        ureq::Error::Transport(t) if quota_exceeded(&t).is_some() => AppError {
+            kind: Kind::Auth,
+            message: transport_message(&t)
+        },
+        // The response came but isn't the JSON it should be
+        ureq::Error::Transport(t) if malformed(&t) => AppError {
+            kind: Kind::Data,
+            message: transport_message(&t)
+        },
+        ureq::Error::Transport(t) => AppError {
+            kind: Kind::Network,
+            message: transport_message(&t)
+        }
+    }
+}
+
+/// Description of a request that got no response, without the url
+pub fn transport_message(t: &ureq::Transport) -> String {
+    if let Some(QuotaExceeded { usage }) = quota_exceeded(t) {
+        return locale::format("error-quota-exceeded", &[
+            ("calls", &usage.calls),
+            ("since", &usage.period_start),
+            ("reset", &usage.next_reset)
+        ]);
+    }
+
+    t.message().map(str::to_string)
+        .or_else(|| t.source().map(|source| source.to_string()))
+        .unwrap_or_else(|| t.kind().to_string())
+}
+
+/// Refusal of the quota of the client
+fn quota_exceeded(t: &ureq::Transport) -> Option<&QuotaExceeded> {
+    t.source()?
+        .downcast_ref::<io::Error>()?
+        .get_ref()?
+        .downcast_ref()
+}
+
+fn malformed(t: &ureq::Transport) -> bool {
+    t.source()
+        .and_then(|source| source.downcast_ref::<io::Error>())
+        .and_then(|err| err.get_ref())
+        .is_some_and(|err| err.is::<serde_json::Error>())
+}
+
+/// Message and exit code of an error that reached `main`
+pub fn report(err: Box<dyn Error>) -> (String, exitcode::ExitCode) {
+    let err = match err.downcast::<ureq::Error>() {
+        Ok(err) => Box::new(request_error(*err)),
+        Err(err) => match err.downcast::<AppError>() {
+            Ok(err) => err,
+            Err(err) => Box::new(AppError { kind: Kind::Internal, message: err.to_string() })
+        }
+    };
+
+    (err.message, err.kind.exit_code())
+}
+
+/// Keeps panic messages for [`panicked`] instead of printing them
+pub fn catch_panics() {
+    panic::set_hook(Box::new(|info| {
+        if let Ok(mut panic) = PANIC.lock() {
+            *panic = Some(info.to_string());
+        }
+    }));
+}
+
+/// Internal error out of a caught panic
+pub fn panicked(payload: Box<dyn Any + Send>) -> Box<dyn Error> {
+    let message = PANIC.lock().ok()
+        .and_then(|mut panic| panic.take())
+        .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default();
+
+    new(Kind::Internal, locale::format("error-internal", &[("error", &message)]))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(status: u16, body: &str) -> AppError {
+        request_error(ureq::Error::Status(status, ureq::Response::new(status, "", body).unwrap()))
+    }
+
+    #[test]
+    fn api_errors() {
+        let location = status(400, r#"{"error":{"code":1006,"message":"No matching location found."}}"#);
+        assert_eq!(Kind::Location, location.kind);
+        assert_eq!("No matching location found.", location.message);
+
+        assert_eq!(Kind::Auth, status(403, r#"{"error":{"code":2007,"message":"API key has exceeded calls per month quota."}}"#).kind);
+        assert_eq!(Kind::Auth, status(401, "").kind);
+        assert_eq!(Kind::Network, status(502, "<html></html>").kind);
+
+        let date = weer_api::chrono::NaiveDate::from_ymd_opt(2023, 10, 1).unwrap();
+        let usage = weer_api::Usage { calls: 900, budget: Some(1000), soft_limit: Some(900), period_start: date, next_reset: date };
+        let refused: ureq::Error = io::Error::other(QuotaExceeded { usage }).into();
+        assert_eq!(Kind::Auth, request_error(refused).kind);
+
+        let json = serde_json::from_str::<serde_json::Value>("<html>").unwrap_err();
+        assert_eq!(Kind::Data, request_error(io::Error::from(json).into()).kind);
+        assert_eq!(Kind::Network, request_error(io::Error::from(io::ErrorKind::ConnectionRefused).into()).kind);
+
+        assert_eq!(exitcode::IOERR, report(io(io::Error::from(io::ErrorKind::BrokenPipe).into())).1);
+        assert_eq!(exitcode::DATAERR, report(io(io::Error::from(io::ErrorKind::InvalidData).into())).1);
+        assert_eq!(exitcode::CONFIG, report(io(new(Kind::Config, "invalid config"))).1);
+
+        let (message, code) = report(new(Kind::Config, "invalid config"));
+        assert_eq!(("invalid config".to_string(), exitcode::CONFIG), (message, code));
+        assert_eq!(exitcode::SOFTWARE, report("unexpected".into()).1);
+    }
+}
@@ -0,0 +1,214 @@
+//! One-line output of `weer now --format`, for status bars and shell prompts
+//!
+//! Placeholders follow wttr.in: `%c` icon, `%C` condition, `%t` temperature, `%f` feels like, `%w` wind,
+//! `%h` humidity, `%p` precipitation, `%u` UV index, `%a` air quality, `%S` sunrise, `%s` sunset, `%l` location
+//! and `%%` a literal `%`.
+
+use std::str::FromStr;
+
+use weer_api::{Astro, ConditionCode, Current, Location, Severity};
+use super::{config::Units, tables};
+
+
+/// Format used when only a preset is given
+pub const DEFAULT_FORMAT: &str = "%c %t";
+
+/// JSON presets, as the custom modules of the status bars expect them
+pub const PRESETS: [&str; 2] = ["waybar", "i3blocks"];
+
+/// Colour markups of `--color`
+pub const COLORS: [&str; 4] = ["none", "ansi", "tmux", "polybar"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    None,
+    Ansi,
+    Tmux,
+    Polybar
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Color::None),
+            "ansi" => Ok(Color::Ansi),
+            "tmux" => Ok(Color::Tmux),
+            "polybar" => Ok(Color::Polybar),
+            _ => Err(format!("expected one of: {}", COLORS.join(", ")))
+        }
+    }
+}
+
+impl Color {
+    fn paint(&self, s: &str, (r, g, b): (u8, u8, u8)) -> String {
+        match self {
+            Color::None => s.to_string(),
+            Color::Ansi => format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, s),
+            Color::Tmux => format!("#[fg=#{:02x}{:02x}{:02x}]{}#[default]", r, g, b, s),
+            Color::Polybar => format!("%{{F#{:02x}{:02x}{:02x}}}{}%{{F-}}", r, g, b, s)
+        }
+    }
+}
+
+/// How a temperature feels, used as colour and as the waybar class
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Feel {
+    Freezing,
+    Cool,
+    Mild,
+    Warm,
+    Hot
+}
+
+impl Feel {
+    pub fn from_celsius(c: f32) -> Self {
+        match c {
+            c if c < 0.0 => Feel::Freezing,
+            c if c < 10.0 => Feel::Cool,
+            c if c < 20.0 => Feel::Mild,
+            c if c < 30.0 => Feel::Warm,
+            _ => Feel::Hot
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Feel::Freezing => "freezing",
+            Feel::Cool => "cool",
+            Feel::Mild => "mild",
+            Feel::Warm => "warm",
+            Feel::Hot => "hot"
+        }
+    }
+
+    fn rgb(&self) -> (u8, u8, u8) {
+        match self {
+            Feel::Freezing => (0x5e, 0x81, 0xac),
+            Feel::Cool => (0x88, 0xc0, 0xd0),
+            Feel::Mild => (0xa3, 0xbe, 0x8c),
+            Feel::Warm => (0xeb, 0xcb, 0x8b),
+            Feel::Hot => (0xbf, 0x61, 0x6a)
+        }
+    }
+
+    /// Colour as `#rrggbb`, for i3blocks
+    pub fn hex(&self) -> String {
+        let (r, g, b) = self.rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+/// Everything a format string can show
+pub struct Status<'a> {
+    pub location: &'a Location,
+    pub current: &'a Current,
+    /// Only there when the format asks for the sunrise or the sunset
+    pub astro: Option<&'a Astro>,
+    pub units: Units
+}
+
+/// Whether `format` shows the sunrise or the sunset, which realtime responses don't have
+pub fn needs_astro(format: &str) -> bool {
+    let mut needs = false;
+    expand(format, |c| {
+        needs |= matches!(c, 'S' | 's');
+        Some(String::new())
+    });
+    needs
+}
+
+pub fn render(format: &str, status: &Status, color: Color) -> String {
+    let current = status.current;
+    let feel = |c: f32| Feel::from_celsius(c).rgb();
+
+    expand(format, |c| Some(match c {
+        'c' => icon(current.condition.code, current.is_day()).to_string(),
+        'C' => current.condition.text.clone(),
+        't' => color.paint(&tables::temperature(current.temp_c, current.temp_f, status.units), feel(current.temp_c)),
+        'f' => color.paint(&tables::temperature(current.feelslike_c, current.feelslike_f, status.units), feel(current.feelslike_c)),
+        'w' => tables::wind(current.wind_kph, current.wind_mph, &current.wind_dir, status.units),
+        'h' => format!("{}%", current.humidity),
+        'p' => tables::precipitation(current.precip_mm, current.precip_in, status.units),
+        'u' => current.uv.to_string(),
+        'a' => current.air_quality.as_ref().map(|air| air.us_epa_index.to_string()).unwrap_or_default(),
+        'S' => status.astro.map(|a| a.sunrise.clone()).unwrap_or_default(),
+        's' => status.astro.map(|a| a.sunset.clone()).unwrap_or_default(),
+        'l' => status.location.name.clone(),
+        _ => return None
+    }))
+}
+
+/// Replaces each `%x` of `format` by `value(x)`, placeholders without a value are kept as they are
+fn expand(format: &str, mut value: impl FnMut(char) -> Option<String>) -> String {
+    let mut out = String::with_capacity(format.len());
+    let mut chars = format.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('%') => out.push('%'),
+            Some(c) => match value(c) {
+                Some(v) => out.push_str(&v),
+                None => {
+                    out.push('%');
+                    out.push(c);
+                }
+            },
+            None => out.push('%')
+        }
+    }
+
+    out
+}
+
+fn icon(code: ConditionCode, is_day: bool) -> &'static str {
+    match code {
+        code if code.is_thunder() => "⛈",
+        code if code.is_snow() => "🌨",
+        code if code.is_fog() => "🌫",
+        code if code.is_precipitation() && code.severity() <= Severity::Light => "🌦",
+        code if code.is_precipitation() => "🌧",
+        ConditionCode::Sunny if is_day => "☀️",
+        ConditionCode::Sunny => "🌙",
+        ConditionCode::PartlyCloudy => "⛅",
+        _ => "☁️"
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders() {
+        let value = |c| match c {
+            't' => Some("12°C".to_string()),
+            'h' => Some("80%".to_string()),
+            _ => None
+        };
+
+        assert_eq!("12°C 80% 100%", expand("%t %h 100%%", value));
+        assert_eq!("%x %t", expand("%x %%t", value));
+        assert_eq!("12°C%", expand("%t%", value));
+
+        assert!(needs_astro("%c %S"));
+        assert!(!needs_astro("%c %t %%s"));
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(Feel::Freezing, Feel::from_celsius(-3.0));
+        assert_eq!(Feel::Mild, Feel::from_celsius(10.0));
+        assert_eq!("#bf616a", Feel::Hot.hex());
+
+        assert_eq!("#[fg=#a3be8c]12#[default]", Color::Tmux.paint("12", Feel::Mild.rgb()));
+        assert_eq!("%{F#a3be8c}12%{F-}", Color::Polybar.paint("12", Feel::Mild.rgb()));
+    }
+}
@@ -1,140 +1,777 @@
 use std::{
     io::{self, Write}, 
     net::IpAddr, 
-    error::Error
+    path::PathBuf,
+    error::Error,
+    thread,
+    time::Duration
 };
 
-use weer_api::{*, chrono::{Utc, TimeZone, Timelike}};
+use weer_api::{*, chrono::{Utc, Local, DateTime, NaiveDate, Timelike}};
 use clap::ArgMatches;
+use serde::Serialize;
 use colored::*;
-use super::{App, tables};
+use crossterm::terminal;
+use super::{App, Config, ConfigFile, chart::{self, Metric}, cmd, completions, config::{self, Units}, error::{self, Kind}, format, notify::{self, Action, Rule, State}, tables, watch, dashboard::Dashboard, locale::{self, text}};
 
 
 pub fn matches_handler(app: &App) -> Result<(), Box<dyn Error>> {
     let out = io::stdout();
 
-    let q = {
-        if let Some(ip) = app.matches.get_one::<IpAddr>("ip") {
-            Query::Ip(Some(ip.clone()))
-        } else if let Some(name) = app.matches.get_one::<String>("city") {
-            Query::City(name.clone())
-        } else if let Some((lat, lon)) = app.matches.get_one::<(f32, f32)>("coords") {
-            Query::Coords(lat.clone(), lon.clone())
-        } else {
-            Query::Ip(None)
-        }
+    let queries = queries(app);
+
+    // Errors left as they are raised are those of the output and of the files of the commands
+    let result = match app.matches.subcommand() {
+        Some(("now", sub_m)) => now(sub_m, &mut out.lock(), app, queries),
+        Some(("forecast", sub_m)) => forecast(sub_m, &mut out.lock(), app, queries),
+        Some(("watch", sub_m)) => watch(sub_m, app, queries),
+        Some(("dashboard", sub_m)) => dashboard(sub_m, app, queries),
+        Some(("history", sub_m)) => history(sub_m, &out, app, single(queries)?),
+        Some(("stats", sub_m)) => stats(sub_m, &out, app, single(queries)?),
+        Some(("degree-days", sub_m)) => degree_days(sub_m, &out, app, single(queries)?),
+        Some(("agro", sub_m)) => agro(sub_m, &out, app, single(queries)?),
+        Some(("search", _)) => search(&out, app, single(queries)?),
+        Some(("future", sub_m)) => future(sub_m, &out, app, single(queries)?),
+        Some(("notify", sub_m)) => notify(sub_m, &out, app, single(queries)?),
+        Some(("config", sub_m)) => config(sub_m, &out, app).map_err(error::config),
+        Some(("profile", sub_m)) => profile(sub_m, &out, app).map_err(error::config),
+        Some(("languages", _)) => languages(&out, app),
+        Some(("quota", _)) => quota(&out, app),
+        Some(("completions", sub_m)) => Ok(completions::generate(*sub_m.get_one("shell").unwrap(), &mut out.lock())?),
+        Some(("man", sub_m)) => completions::man(sub_m.get_one::<PathBuf>("dir").map(|p| p.as_path()), &mut out.lock()),
+        Some(("complete", sub_m)) => Ok(completions::values(sub_m.get_one::<String>("kind").unwrap(), &app.file, &mut out.lock())?),
+        Some((name, _)) => Err(error::new(Kind::Internal, format!("unhandled command: {}", name))),
+        None => Ok(())
     };
 
-    match app.matches.subcommand() {
-        Some(("forecast", sub_m)) => forecast(sub_m, &out, &app, q),
-        Some(("history", sub_m)) => history(sub_m, &out, &app, q),
-        Some(("search", _)) => search(&out, &app, q),
-        Some(("config", sub_m)) => {
-            let mut cfg = app.cfg.clone();
-            if let Some(api_key) = sub_m.get_one::<String>("api_key") {
-                cfg.api_key = api_key.clone()
-            }
+    result.map_err(error::io)
+}
 
-            if let Some(lang) = sub_m.get_one::<String>("lang") {
-                cfg.lang = Some(serde_json::from_value(serde_json::json![lang]).unwrap())
-            }
+/// Locations given on the command line, or the configured one
+fn queries(app: &App) -> Vec<Query> {
+    if let Some(ip) = app.matches.get_one::<IpAddr>("ip") {
+        vec![Query::Ip(Some(*ip))]
+    } else if let Some(names) = app.matches.get_many::<String>("city") {
+        names.map(|name| Query::City(name.clone())).collect()
+    } else if let Some(coords) = app.matches.get_many::<(f32, f32)>("coords") {
+        coords.map(|(lat, lon)| Query::Coords(*lat, *lon)).collect()
+    } else if let Some(location) = &app.cfg.location {
+        vec![Query::City(location.clone())]
+    } else {
+        vec![Query::Ip(None)]
+    }
+}
+
+/// The only location of commands that can't compare several
+fn single(mut queries: Vec<Query>) -> Result<Query, Box<dyn Error>> {
+    match queries.len() {
+        1 => Ok(queries.remove(0)),
+        _ => Err(error::new(Kind::Usage, text("error-several-locations")))
+    }
+}
 
-            confy::store("weer", "config", cfg)?;
-            Ok(())
+fn now(sub_m: &ArgMatches, out: &mut impl Write, app: &App, mut queries: Vec<Query>) -> Result<(), Box<dyn Error>> {
+    if sub_m.contains_id("format") || sub_m.contains_id("preset") {
+        if queries.len() > 1 {
+            return Err(error::new(Kind::Usage, text("error-format-several-locations")));
         }
-        // Some(("future", _sub_m)) => todo!(),
-        Some((&_, _)) => todo!(),
-        None => Ok(())
+
+        return status_line(sub_m, out, app, queries.remove(0));
+    }
+
+    let aqi = sub_m.get_one::<bool>("aqi").unwrap();
+    let client = app.client()?;
+    let lang = app.cfg.lang();
+
+    let call = |q: Query| {
+        let mut req = client.realtime();
+        req.query(q);
+
+        if *aqi {
+            req.aqi();
+        }
+
+        if let Some(lang) = lang {
+            req.lang(lang);
+        }
+
+        req.call()
+    };
+
+    if queries.len() > 1 {
+        tables::compare_current_table(&fetch_all(queries, call)?, &app.cfg).print(out)?;
+        return Ok(());
     }
+
+    let resp = call(queries.remove(0))?;
+
+    writeln!(out, "{}:", text("location").bold())?;
+    tables::location_table(resp.location).print(out)?;
+
+    writeln!(out, "{}:", text("current").bold())?;
+    tables::current_table(resp.current, &app.cfg)?.print(out)?;
+
+    Ok(())
 }
 
-fn forecast(sub_m: &ArgMatches, out: &io::Stdout, app: &App, q: Query) -> Result<(), Box<dyn Error>> {
-    let mut out = out.lock();
+/// `now --format` and `--preset`
+fn status_line(sub_m: &ArgMatches, out: &mut impl Write, app: &App, q: Query) -> Result<(), Box<dyn Error>> {
+    let template = sub_m.get_one::<String>("format").map_or(format::DEFAULT_FORMAT, |f| f.as_str());
+    let color = sub_m.get_one::<String>("color").unwrap().parse::<format::Color>()?;
+    let aqi = *sub_m.get_one::<bool>("aqi").unwrap() || template.contains("%a");
+    let client = app.client()?;
+    let lang = app.cfg.lang();
+
+    // Only forecasts have the sunrise and the sunset
+    let (location, current, astro) = if format::needs_astro(template) {
+        let mut req = client.forecast();
+        req.query(q)
+            .dt(Utc::now())
+            .days(1)
+            .aqi(aqi);
+
+        if let Some(lang) = lang {
+            req.lang(lang);
+        }
+
+        let resp = req.call()?;
+        let astro = resp.forecast.forecast_day.into_iter().next().map(|fd| fd.astro);
+        (resp.location, resp.current, astro)
+    } else {
+        let mut req = client.realtime();
+        req.query(q);
+
+        if aqi {
+            req.aqi();
+        }
+
+        if let Some(lang) = lang {
+            req.lang(lang);
+        }
+
+        let resp = req.call()?;
+        (resp.location, resp.current, None)
+    };
+
+    let status = format::Status {
+        location: &location,
+        current: &current,
+        astro: astro.as_ref(),
+        units: app.cfg.units()
+    };
+
+    let line = format::render(template, &status, color);
+    let feel = format::Feel::from_celsius(current.temp_c);
 
+    match sub_m.get_one::<String>("preset").map(String::as_str) {
+        Some("waybar") => writeln!(out, "{}", serde_json::json!({
+            "text": line,
+            "tooltip": format::render("%l: %C, %t (%f), %w, %h", &status, format::Color::None),
+            "class": feel.name(),
+            "alt": current.condition.code.code().to_string()
+        }))?,
+        Some("i3blocks") => writeln!(out, "{}", serde_json::json!({
+            "full_text": line,
+            "short_text": format::render(format::DEFAULT_FORMAT, &status, format::Color::None),
+            "color": feel.hex()
+        }))?,
+        _ => writeln!(out, "{}", line)?
+    }
+
+    Ok(())
+}
+
+fn forecast(sub_m: &ArgMatches, out: &mut impl Write, app: &App, mut queries: Vec<Query>) -> Result<(), Box<dyn Error>> {
     let days = sub_m.get_one::<u8>("days").unwrap();
     let hour = sub_m.get_one::<u32>("hour").unwrap_or(&0);
     let aqi = sub_m.get_one::<bool>("aqi").unwrap();
     let alerts = sub_m.get_one::<bool>("alerts").unwrap();
+    let chart = chart_metric(sub_m)?;
 
-    let dt = if let Some(dt) = sub_m.get_one::<String>("dt") {
-        Utc.datetime_from_str(dt, "%Y-%m-%d")?.with_hour(hour.clone()).unwrap()
-    } else {
-        Utc::now().with_hour(hour.clone()).unwrap()
+    let dt = match sub_m.get_one::<String>("dt") {
+        Some(dt) => parse_date(dt, *hour)?,
+        None => Utc::now().with_hour(*hour).ok_or_else(|| error::new(Kind::Usage, text("error-invalid-hour")))?
     };
 
-    let mut req = app.client.forecast();
-    req.query(q)
-        .dt(dt)
-        .days(days.clone())
-        .hour(true)
-        .aqi(*aqi)
-        .alerts(*alerts);
+    let client = app.client()?;
+    let lang = app.cfg.lang();
 
-    if let Some(lang) = app.cfg.lang {
-        req.lang(lang);
+    let call = |q: Query| {
+        let mut req = client.forecast();
+        req.query(q)
+            .dt(dt)
+            .days(*days)
+            .hour(single_hour(sub_m, chart))
+            .aqi(*aqi)
+            .alerts(*alerts);
+
+        if let Some(lang) = lang {
+            req.lang(lang);
+        }
+
+        req.call()
+    };
+
+    if queries.len() > 1 {
+        if chart.is_some() {
+            return Err(error::new(Kind::Usage, text("error-chart-several-locations")));
+        }
+
+        tables::compare_forecast_table(&fetch_all(queries, call)?, &app.cfg).print(out)?;
+        return Ok(());
     }
 
-    let resp = req.call()?;
+    let resp = call(queries.remove(0))?;
 
-    writeln!(out, "{}:", "Location".bold())?;
-    tables::location_table(resp.location).print(&mut out)?;
+    writeln!(out, "{}:", text("location").bold())?;
+    tables::location_table(resp.location).print(out)?;
 
-    writeln!(out, "{}:", "Current".bold())?;
-    tables::current_table(resp.current)?.print(&mut out)?;
+    writeln!(out, "{}:", text("current").bold())?;
+    tables::current_table(resp.current, &app.cfg)?.print(out)?;
     
-    for fd in resp.forecast.forecast_day.iter() {
-        writeln!(out, "{} - {}:", "Forecast".bold(), fd.date.italic())?;
-        let table = tables::forecastday_table(fd)?;
-        table.print(&mut out)?;
-    }
+    print_days(out, &resp.forecast.forecast_day, chart, &app.cfg)
+}
 
-    Ok(())
+fn watch(sub_m: &ArgMatches, app: &App, queries: Vec<Query>) -> Result<(), Box<dyn Error>> {
+    let minutes = sub_m.get_one::<u64>("interval")
+        .copied()
+        .unwrap_or_else(|| app.cfg.watch_interval().max(config::MIN_WATCH_INTERVAL));
+
+    // `weer watch` alone watches the current weather
+    let default = cmd::now().get_matches_from(["now"]);
+    let (name, view_m) = sub_m.subcommand().unwrap_or(("now", &default));
+
+    watch::run(Duration::from_secs(minutes * 60), |out| match name {
+        "forecast" => forecast(view_m, out, app, queries.clone()),
+        _ => now(view_m, out, app, queries.clone())
+    })
+}
+
+fn dashboard(sub_m: &ArgMatches, app: &App, queries: Vec<Query>) -> Result<(), Box<dyn Error>> {
+    // Saved locations are only used when none is given on the command line
+    let given = ["ip", "city", "coords"].iter().any(|id| app.matches.contains_id(id));
+    let locations = match &app.cfg.locations {
+        Some(saved) if !given && !saved.is_empty() => saved.iter().map(|l| Query::City(l.clone())).collect(),
+        _ => queries
+    };
+
+    Dashboard::new(app.client()?, locations)
+        .lang(app.cfg.lang())
+        .days(*sub_m.get_one::<u8>("days").unwrap())
+        .refresh(Duration::from_secs(app.cfg.watch_interval() * 60))
+        .units(app.cfg.units())
+        .run()
+}
+
+/// Requests every location at once, a failure only ends up in the column of its location
+///
+/// A panic of a request is an internal error of the whole command.
+fn fetch_all<T: Send>(
+    queries: Vec<Query>,
+    call: impl Fn(Query) -> Result<T, ureq::Error> + Sync
+) -> Result<Vec<tables::Column<T>>, Box<dyn Error>> {
+    thread::scope(|s| {
+        let call = &call;
+        let handles: Vec<_> = queries.into_iter()
+            .map(|q| (q.to_string(), s.spawn(move || call(q).map_err(describe_error))))
+            .collect();
+
+        // Every thread is joined before returning, the scope would panic again on the ones left
+        let joined: Vec<_> = handles.into_iter().map(|(name, handle)| (name, handle.join())).collect();
+        joined.into_iter()
+            .map(|(name, result)| Ok((name, result.map_err(error::panicked)?)))
+            .collect()
+    })
 }
 
 fn history(sub_m: &ArgMatches, out: &io::Stdout, app: &App, q: Query) -> Result<(), Box<dyn Error>> {
     let mut out = out.lock();
 
     let hour = sub_m.get_one::<u32>("hour").unwrap_or(&0);
-    let dt = if let Some(dt) = sub_m.get_one::<String>("dt") {
-        Utc.datetime_from_str(dt, "%Y-%m-%d")?.with_hour(hour.clone()).unwrap()
-    } else {
-        Utc::now().with_hour(hour.clone()).unwrap()
+    let chart = chart_metric(sub_m)?;
+    let dt = match sub_m.get_one::<String>("dt") {
+        Some(dt) => parse_date(dt, *hour)?,
+        None => Utc::now().with_hour(*hour).ok_or_else(|| error::new(Kind::Usage, text("error-invalid-hour")))?
     };
 
-    let mut req = app.client.history();
+    let resp = match sub_m.get_one::<String>("end_dt") {
+        // Split into as many requests as the range needs
+        Some(end_dt) => {
+            let end_dt = parse_date(end_dt, *hour)?;
+            if end_dt < dt {
+                return Err(error::new(Kind::Usage, text("error-date-range")));
+            }
+
+            let mut req = app.client()?.history_range(dt, end_dt);
+            req.query(q)
+                .hour(single_hour(sub_m, chart));
+
+            if let Some(lang) = app.cfg.lang() {
+                req.lang(lang);
+            }
+            req.call()?
+        }
+        None => {
+            let mut req = app.client()?.history();
+            req.query(q)
+                .dt(dt)
+                .hour(single_hour(sub_m, chart));
+
+            if let Some(lang) = app.cfg.lang() {
+                req.lang(lang);
+            }
+            req.call()?
+        }
+    };
+
+    writeln!(out, "{}:", text("location").bold())?;
+    tables::location_table(resp.location).print(&mut out)?;
+
+    print_days(&mut out, &resp.forecast.forecast_day, chart, &app.cfg)
+}
+
+/// JSON output of `weer stats`
+#[derive(Serialize)]
+struct StatsReport<'a> {
+    location: &'a Location,
+    #[serde(flatten)]
+    stats: &'a Stats
+}
+
+fn stats(sub_m: &ArgMatches, out: &io::Stdout, app: &App, q: Query) -> Result<(), Box<dyn Error>> {
+    let mut out = out.lock();
+
+    // Hourly data is needed for the gusts and the sunny hours
+    let resp = history_span(sub_m, app, q)?;
+    let stats = Stats::new(&resp.forecast.forecast_day)
+        .ok_or_else(|| error::new(Kind::Data, "no history returned"))?;
+
+    if *sub_m.get_one::<bool>("json").unwrap() {
+        let report = StatsReport { location: &resp.location, stats: &stats };
+        writeln!(out, "{}", serde_json::to_string_pretty(&report)?)?;
+        return Ok(());
+    }
+
+    writeln!(out, "{}:", text("location").bold())?;
+    tables::location_table(resp.location).print(&mut out)?;
+
+    writeln!(out, "{}:", text("stats").bold())?;
+    tables::stats_table(&stats, app.cfg.units()).print(&mut out)?;
+
+    Ok(())
+}
+
+fn degree_days(sub_m: &ArgMatches, out: &io::Stdout, app: &App, q: Query) -> Result<(), Box<dyn Error>> {
+    let mut out = out.lock();
+    let units = app.cfg.units();
+
+    // Bases are given in the units of the config
+    let base = |id: &str| celsius(sub_m.get_one::<f32>(id).copied().unwrap_or(match units {
+        Units::Metric => 18.0,
+        Units::Imperial => 65.0
+    }), units);
+
+    let mut degree_days = DegreeDays::new();
+    degree_days.heating_base(base("heating-base"))
+        .cooling_base(base("cooling-base"))
+        .method(*sub_m.get_one::<DegreeDayMethod>("method").unwrap());
+
+    let resp = history_span(sub_m, app, q)?;
+    let days = resp.degree_days(&degree_days);
+
+    if *sub_m.get_one::<bool>("csv").unwrap() {
+        let temp = match units {
+            Units::Metric => "temp_c",
+            Units::Imperial => "temp_f"
+        };
+        writeln!(out, "date,{},hdd,cdd,cumulative_hdd,cumulative_cdd", temp)?;
+        for (day, values) in days.iter().zip(tables::degree_day_values(&days, units)) {
+            let [temp, hdd, cdd, cumulative_hdd, cumulative_cdd] = values;
+            writeln!(out, "{},{},{},{},{},{}", day.date, temp, hdd, cdd, cumulative_hdd, cumulative_cdd)?;
+        }
+        return Ok(());
+    }
+
+    writeln!(out, "{}:", text("location").bold())?;
+    tables::location_table(resp.location).print(&mut out)?;
+
+    writeln!(out, "{}:", text("degree-days").bold())?;
+    tables::degree_days_table(&days, units).print(&mut out)?;
+
+    Ok(())
+}
+
+fn agro(sub_m: &ArgMatches, out: &io::Stdout, app: &App, q: Query) -> Result<(), Box<dyn Error>> {
+    let mut out = out.lock();
+    let units = app.cfg.units();
+
+    let base = sub_m.get_one::<f32>("base").copied().unwrap_or(match units {
+        Units::Metric => 10.0,
+        Units::Imperial => 50.0
+    });
+    let cap = sub_m.get_one::<f32>("cap").copied().unwrap_or(match units {
+        Units::Metric => 30.0,
+        Units::Imperial => 86.0
+    });
+
+    let mut gdd = GrowingDegreeDays::new();
+    gdd.base(celsius(base, units))
+        .cap((!*sub_m.get_one::<bool>("no-cap").unwrap()).then(|| celsius(cap, units)));
+
+    // Hourly data is needed for the evapotranspiration
+    let resp = history_span(sub_m, app, q)?;
+    let days = &resp.forecast.forecast_day;
+    let gdd = resp.growing_degree_days(&gdd);
+    let et0: Vec<Option<f32>> = days.iter()
+        .map(|fd| fd.reference_evapotranspiration(&resp.location.coords))
+        .collect();
+
+    writeln!(out, "{}:", text("location").bold())?;
+    tables::location_table(resp.location).print(&mut out)?;
+
+    writeln!(out, "{}:", text("agro").bold())?;
+    tables::agro_table(days, &gdd, &et0, units).print(&mut out)?;
+
+    Ok(())
+}
+
+/// Temperature of the command line in celsius
+fn celsius(t: f32, units: Units) -> f32 {
+    match units {
+        Units::Metric => t,
+        Units::Imperial => (t - 32.0) / 1.8
+    }
+}
+
+/// History of every hour of the days from `--from` to `--to`
+fn history_span(sub_m: &ArgMatches, app: &App, q: Query) -> Result<History, Box<dyn Error>> {
+    let from = parse_date(sub_m.get_one::<String>("from").unwrap(), 0)?;
+    let to = parse_date(sub_m.get_one::<String>("to").unwrap(), 0)?;
+    if to < from {
+        return Err(error::new(Kind::Usage, text("error-from-to")));
+    }
+
+    let mut req = app.client()?.history_range(from, to);
+    req.query(q);
+
+    if let Some(lang) = app.cfg.lang() {
+        req.lang(lang);
+    }
+
+    Ok(req.call()?)
+}
+
+fn future(sub_m: &ArgMatches, out: &io::Stdout, app: &App, q: Query) -> Result<(), Box<dyn Error>> {
+    let mut out = out.lock();
+
+    let chart = chart_metric(sub_m)?;
+    let dt = parse_date(sub_m.get_one::<String>("dt").unwrap(), 0)?;
+
+    let mut req = app.client()?.future();
     req.query(q)
-        .dt(dt)
-        .hour(true);
-    
-    if let Some(end_dt) = sub_m.get_one::<String>("end_dt") {
-        req.end_dt(Utc.datetime_from_str(end_dt, "%Y-%m-%d")?);
+        .dt(dt);
+
+    if let Some(lang) = app.cfg.lang() {
+        req.lang(lang);
     }
 
     let resp = req.call()?;
 
-    writeln!(out, "{}:", "Location".bold())?;
+    writeln!(out, "{}:", text("location").bold())?;
     tables::location_table(resp.location).print(&mut out)?;
 
-    for fd in resp.forecast.forecast_day.iter() {
-        writeln!(out, "{} - {}:", "Forecast".bold(), fd.date.italic())?;
-        let table = tables::forecastday_table(fd)?;
-        table.print(&mut out)?;
+    print_days(&mut out, &resp.forecast.forecast_day, chart, &app.cfg)
+}
+
+fn notify(sub_m: &ArgMatches, out: &io::Stdout, app: &App, q: Query) -> Result<(), Box<dyn Error>> {
+    let mut out = out.lock();
+    let rules: Vec<&Rule> = sub_m.get_many::<Rule>("rule").unwrap().collect();
+
+    let mut actions = vec![];
+    if let Some(command) = sub_m.get_one::<String>("exec") {
+        actions.push(Action::Command(command.clone()));
+    }
+    if let Some(url) = sub_m.get_one::<String>("webhook") {
+        actions.push(Action::Webhook(url.clone()));
+    }
+    if *sub_m.get_one::<bool>("desktop").unwrap() || actions.is_empty() {
+        actions.push(Action::Desktop);
+    }
+
+    // The clock of the machine is close enough to pick how many days to ask for
+    let days = rules.iter().map(|r| r.days(Local::now().naive_local())).max().unwrap_or(1);
+
+    let mut req = app.client()?.forecast();
+    req.query(q)
+        .dt(Utc::now())
+        .days(days.max(2))
+        .alerts(true);
+
+    if let Some(lang) = app.cfg.lang() {
+        req.lang(lang);
+    }
+
+    let forecast = req.call()?;
+    let now = notify::local_now(&forecast);
+    let mut state = State::load();
+
+    for event in rules.iter().flat_map(|rule| rule.check(&forecast, now)) {
+        if !state.is_new(&event) {
+            continue;
+        }
+
+        writeln!(out, "{}", event.summary)?;
+
+        // The event is only remembered if it reached somewhere
+        let mut reported = false;
+        for action in actions.iter() {
+            match action.run(&event) {
+                Ok(()) => reported = true,
+                Err(err) => eprintln!("{}", err)
+            }
+        }
+
+        if reported {
+            state.remember(&event);
+        }
+    }
+
+    state.store(now)
+}
+
+/// A chart of the days when `--chart` is given, a table per day otherwise
+fn print_days(
+    out: &mut impl Write,
+    days: &[ForecastDay],
+    chart: Option<Metric>,
+    cfg: &Config
+) -> Result<(), Box<dyn Error>> {
+    if let Some(metric) = chart {
+        let width = terminal::size().map_or(80, |(width, _)| width).max(40);
+        if let Some(chart) = chart::chart(days, metric, cfg.units(), width) {
+            write!(out, "{}", chart)?;
+        }
+
+        return Ok(());
+    }
+
+    for fd in days {
+        writeln!(out, "{} - {}:", text("forecast").bold(), fd.date.italic())?;
+        tables::forecastday_table(fd, cfg)?.print(out)?;
     }
 
     Ok(())
 }
 
+fn chart_metric(sub_m: &ArgMatches) -> Result<Option<Metric>, Box<dyn Error>> {
+    Ok(sub_m.get_one::<String>("chart").map(|m| m.parse()).transpose()?)
+}
+
+/// Whether the request restricts the response to the hour of `dt`, charts want every hour unless one is asked for
+fn single_hour(sub_m: &ArgMatches, chart: Option<Metric>) -> bool {
+    chart.is_none() || sub_m.contains_id("hour")
+}
+
 fn search(out: &io::Stdout, app: &App, q: Query) -> Result<(), Box<dyn Error>> {
     let mut out = out.lock();
     
-    let resp = app.client.search()
+    let resp = app.client()?.search()
         .query(q)
         .call()?;
     
     for location in resp {
-        writeln!(out, "{}: {}", "Location".bold(), location.name.italic())?;
+        writeln!(out, "{}: {}", text("location").bold(), location.name.italic())?;
         tables::location_table(location).print(&mut out)?;
     }
     
     Ok(())
 }
+
+fn config(sub_m: &ArgMatches, out: &io::Stdout, app: &App) -> Result<(), Box<dyn Error>> {
+    let mut out = out.lock();
+    let mut file = app.file.clone();
+    let own = file.profile_mut(&app.profile)?;
+
+    match sub_m.subcommand() {
+        Some(("get", m)) => {
+            let key = m.get_one::<String>("key").unwrap();
+            match app.cfg.get_or_default(key)? {
+                Some(value) => writeln!(out, "{}", value)?,
+                None => return Err(locale::format("error-key-not-set", &[("key", key)]).into())
+            }
+        }
+        Some(("set", m)) => {
+            own.set(m.get_one::<String>("key").unwrap(), m.get_one::<String>("value").unwrap())?;
+            file.store()?;
+        }
+        Some(("unset", m)) => {
+            own.unset(m.get_one::<String>("key").unwrap())?;
+            file.store()?;
+        }
+        Some(("list", _)) => {
+            writeln!(out, "{}: {}", text("profile").bold(), app.profile.italic())?;
+            for key in config::KEYS {
+                let value = match (key, app.cfg.get_or_default(key)?) {
+                    (_, None) => text("unset").dimmed().to_string(),
+                    ("api_key", Some(api_key)) => mask(&api_key),
+                    (_, Some(value)) => value
+                };
+
+                if own.get(key)?.is_none() && app.cfg.get(key)?.is_some() {
+                    writeln!(out, "{} = {} {}", key.bold(), value, text("inherited").dimmed())?;
+                } else {
+                    writeln!(out, "{} = {}", key.bold(), value)?;
+                }
+            }
+        }
+        Some(("path", _)) => writeln!(out, "{}", ConfigFile::path()?.display())?,
+        Some(("reset", _)) => {
+            *own = Config::default();
+            file.store()?;
+        }
+        Some(("validate", _)) => {
+            // The cache could answer for a revoked key
+            let client = app.uncached_client()?;
+            match client.realtime().query(Query::City("London".to_string())).call() {
+                Ok(_) => writeln!(out, "{}", text("api-key-valid").green())?,
+                Err(ureq::Error::Status(code, resp)) => {
+                    return Err(error::new(Kind::Auth, locale::format("error-api-key-rejected", &[
+                        ("code", &code),
+                        ("message", &error::api_error(resp).1)
+                    ])))
+                }
+                Err(err) => return Err(err.into())
+            }
+        }
+        _ => unreachable!("config requires a subcommand")
+    }
+
+    Ok(())
+}
+
+fn profile(sub_m: &ArgMatches, out: &io::Stdout, app: &App) -> Result<(), Box<dyn Error>> {
+    let mut out = out.lock();
+    let mut file = app.file.clone();
+
+    match sub_m.subcommand() {
+        Some(("list", _)) => {
+            for name in file.profile_names() {
+                if name == app.profile {
+                    writeln!(out, "{} {}", "*".green(), name.bold())?;
+                } else {
+                    writeln!(out, "  {}", name)?;
+                }
+            }
+        }
+        Some(("create", m)) => {
+            file.create_profile(m.get_one::<String>("name").unwrap())?;
+            file.store()?;
+        }
+        Some(("copy", m)) => {
+            file.copy_profile(m.get_one::<String>("src").unwrap(), m.get_one::<String>("dst").unwrap())?;
+            file.store()?;
+        }
+        Some(("delete", m)) => {
+            file.delete_profile(m.get_one::<String>("name").unwrap())?;
+            file.store()?;
+        }
+        _ => unreachable!("profile requires a subcommand")
+    }
+
+    Ok(())
+}
+
+fn languages(out: &io::Stdout, app: &App) -> Result<(), Box<dyn Error>> {
+    let mut out = out.lock();
+    tables::languages_table(app.cfg.lang()).print(&mut out)?;
+    Ok(())
+}
+
+fn quota(out: &io::Stdout, app: &App) -> Result<(), Box<dyn Error>> {
+    let mut out = out.lock();
+    let api_key = app.api_key()?;
+    let quota = app.cfg.quota().ok_or_else(|| error::new(Kind::Config, text("error-no-data-dir")))?;
+    let usage = quota.usage(&api_key)?;
+
+    let calls = match usage.budget {
+        Some(budget) => format!("{} / {} ({:.1}%)", usage.calls, budget, usage.calls as f64 * 100.0 / budget.max(1) as f64),
+        None => format!("{} ({})", usage.calls, text("quota-no-budget").dimmed())
+    };
+    let calls = match usage.is_limited() {
+        true => calls.red().to_string(),
+        false => calls
+    };
+
+    writeln!(out, "{}: {}", text("quota-key").bold(), mask(&api_key))?;
+    writeln!(out, "{}: {}", text("quota-calls").bold(), calls)?;
+    writeln!(out, "{}: {}", text("quota-period").bold(), usage.period_start)?;
+    writeln!(out, "{}: {}", text("quota-reset").bold(), usage.next_reset)?;
+    if let Some(limit) = usage.soft_limit {
+        writeln!(out, "{}: {} ({}%, {})", text("quota-soft-limit").bold(), limit, app.cfg.quota_soft_limit(), config::limit_action_name(quota.limit_action()))?;
+    }
+    match app.cfg.rate_limit {
+        Some(calls) => writeln!(out, "{}: {}", text("quota-rate-limit").bold(), locale::format("quota-per-minute", &[("calls", &calls)]))?,
+        None => writeln!(out, "{}: {}", text("quota-rate-limit").bold(), text("unset").dimmed())?
+    }
+
+    Ok(())
+}
+
+/// Short description of a failed request, the url is left out as it holds the api key
+pub fn describe_error(err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(_, resp) => error::api_error(resp).1,
+        ureq::Error::Transport(t) => error::transport_message(&t)
+    }
+}
+
+fn mask(secret: &str) -> String {
+    let hidden = secret.chars().count().saturating_sub(4);
+    secret.chars()
+        .enumerate()
+        .map(|(i, c)| if i < hidden { '*' } else { c })
+        .collect()
+}
+
+fn parse_date(s: &str, hour: u32) -> Result<DateTime<Utc>, Box<dyn Error>> {
+    let dt = NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map_err(|err| error::new(Kind::Usage, locale::format("error-invalid-date", &[("date", &s), ("error", &err)])))?
+        .and_hms_opt(hour, 0, 0)
+        .ok_or_else(|| error::new(Kind::Usage, text("error-invalid-hour")))?;
+    Ok(DateTime::from_naive_utc_and_offset(dt, Utc))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weer_api::mock::{self, MockServer};
+
+    #[test]
+    fn fetch_locations() {
+        let server = MockServer::start().unwrap();
+        let client = server.client("api_key");
+        let queries = vec![Query::City("London".to_string()), Query::City(mock::UNKNOWN_LOCATION.to_string())];
+
+        let columns = fetch_all(queries, |q| client.realtime().query(q).call()).unwrap();
+        assert_eq!("London", columns[0].1.as_ref().unwrap().location.name);
+        assert_eq!(mock::UNKNOWN_LOCATION, columns[1].0);
+        assert_eq!(Some("No matching location found."), columns[1].1.as_ref().err().map(String::as_str));
+
+        let err = server.client(mock::INVALID_KEY).realtime().query(Query::Ip(None)).call().unwrap_err();
+        assert_eq!("API key provided is invalid", describe_error(err));
+    }
+
+    #[test]
+    fn fetch_panics() {
+        let queries = vec![Query::City("London".to_string()), Query::City("Paris".to_string())];
+        let err = fetch_all(queries, |q| match q {
+            Query::City(city) if city == "Paris" => panic!("bug in a request"),
+            _ => Ok(())
+        }).unwrap_err();
+
+        let (message, code) = error::report(err);
+        assert_eq!(exitcode::SOFTWARE, code);
+        assert!(message.contains("bug in a request"), "{}", message);
+    }
+}
@@ -1,32 +1,47 @@
 use std::{
     io::Read, 
     error::Error,
-    num::NonZeroU32
+    num::NonZeroU32,
+    collections::HashMap,
+    sync::{Mutex, OnceLock}
 };
 use image::DynamicImage;
 use artem::{options::OptionBuilder};
+use super::error::{self, Kind};
 
 
 fn load_image(url: &str) -> Result<DynamicImage, Box<dyn Error>> {
     let resp = ureq::get(url).call()?;
 
     let len: usize = resp.header("content-length")
-        .unwrap()
-        .parse()?;
+        .and_then(|len| len.parse().ok())
+        .unwrap_or_default();
 
     let mut bytes: Vec<u8> = Vec::with_capacity(len);
     resp.into_reader()
         .take(10_000_000)
-        .read_to_end(&mut bytes)?;
+        .read_to_end(&mut bytes)
+        .map_err(|err| error::new(Kind::Network, err))?;
 
-    Ok(image::load_from_memory(&bytes)?)
+    image::load_from_memory(&bytes).map_err(|err| error::new(Kind::Data, err))
 }
 
+/// Icons already drawn, a forecast repeats the same few and `weer watch` redraws them on every refresh
+static ICONS: OnceLock<Mutex<HashMap<String, String>>> = OnceLock::new();
+
 pub fn convert(icon: &str) -> Result<String, Box<dyn Error>> {
+    let icons = ICONS.get_or_init(Default::default);
+    if let Some(art) = icons.lock().unwrap().get(icon) {
+        return Ok(art.clone());
+    }
+
     let mut options_builder = OptionBuilder::new();
     options_builder.characters(r#"$@B%8&WM#*oahkbdpqwmZO0QLCJUYXzcvunxrjft/\|()1{}[]?-_+~<>i!lI;:,"^`'. "#.to_string());
     options_builder.target_size(NonZeroU32::new(30).unwrap());
 
     let image = load_image(format!("https:{}", icon).as_str())?;
-    Ok(artem::convert(image, options_builder.build()))
+    let art = artem::convert(image, options_builder.build());
+    icons.lock().unwrap().insert(icon.to_string(), art.clone());
+
+    Ok(art)
 }
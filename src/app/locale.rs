@@ -0,0 +1,178 @@
+//! Message catalogue of the CLI
+//!
+//! Catalogues are [Fluent](https://projectfluent.org/) resources read with `fluent-bundle`. Messages missing from a
+//! catalogue fall back to English.
+
+use std::{borrow::Cow, fmt::Display, sync::OnceLock};
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue, concurrent::FluentBundle};
+use unic_langid::LanguageIdentifier;
+use weer_api::{Language, MoonPhase};
+
+const ENGLISH: &str = include_str!("locale/en.ftl");
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+
+struct Locale {
+    /// Bundle of the language then the English one
+    bundles: Vec<FluentBundle<FluentResource>>
+}
+
+impl Locale {
+    fn new(lang: Option<Language>) -> Self {
+        let translated = lang.and_then(|lang| Some(bundle(lang.content(), source(lang)?)));
+
+        Self {
+            bundles: translated.into_iter().chain([bundle("en", ENGLISH)]).collect()
+        }
+    }
+
+    fn get<'a>(&'a self, key: &'a str, args: Option<&FluentArgs>) -> Cow<'a, str> {
+        self.bundles.iter()
+            .find_map(|bundle| {
+                let pattern = bundle.get_message(key)?.value()?;
+                Some(bundle.format_pattern(pattern, args, &mut Vec::new()))
+            })
+            .unwrap_or(Cow::Borrowed(key))
+    }
+}
+
+/// Catalogue of a language, `None` for the ones that aren't translated yet
+fn source(lang: Language) -> Option<&'static str> {
+    match lang {
+        Language::German => Some(include_str!("locale/de.ftl")),
+        Language::French => Some(include_str!("locale/fr.ftl")),
+        Language::Portuguese => Some(include_str!("locale/pt.ftl")),
+        Language::Spanish => Some(include_str!("locale/es.ftl")),
+        _ => None
+    }
+}
+
+fn bundle(id: &str, source: &str) -> FluentBundle<FluentResource> {
+    let resource = FluentResource::try_new(source.to_string()).expect("the bundled catalogues are valid Fluent");
+    let lang: LanguageIdentifier = id.parse().expect("the bundled catalogues have a valid language");
+
+    let mut bundle = FluentBundle::new_concurrent(vec![lang]);
+    // Placeables are not wrapped in bidi isolation marks, terminals print them
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).expect("the bundled catalogues have no duplicate messages");
+    bundle
+}
+
+fn locale() -> &'static Locale {
+    LOCALE.get_or_init(|| Locale::new(None))
+}
+
+/// Selects the language of every message
+///
+/// Only the first call has an effect, messages are in English until then.
+pub fn init(lang: Option<Language>) {
+    let _ = LOCALE.set(Locale::new(lang));
+}
+
+/// Message without variables
+pub fn text(key: &'static str) -> Cow<'static, str> {
+    locale().get(key, None)
+}
+
+/// Message with its `$name` variables taken from `args`, numbers can be used by selectors
+pub fn format(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let values: Vec<_> = args.iter().map(|(_, value)| value.to_string()).collect();
+    let args = args.iter().zip(&values)
+        .map(|((name, _), value)| (*name, FluentValue::try_number(value)))
+        .collect();
+
+    locale().get(key, Some(&args)).into_owned()
+}
+
+pub fn yes_no(value: bool) -> Cow<'static, str> {
+    text(if value { "yes" } else { "no" })
+}
+
+pub fn moon_phase(phase: &MoonPhase) -> Cow<'static, str> {
+    text(match phase {
+        MoonPhase::NewMoon => "moon-new-moon",
+        MoonPhase::WaxingCrescent => "moon-waxing-crescent",
+        MoonPhase::FirstQuarter => "moon-first-quarter",
+        MoonPhase::WaxingGibbous => "moon-waxing-gibbous",
+        MoonPhase::FullMoon => "moon-full-moon",
+        MoonPhase::WaningGibbous => "moon-waning-gibbous",
+        MoonPhase::LastQuarter => "moon-last-quarter",
+        MoonPhase::WaningCrescent => "moon-waning-crescent",
+        MoonPhase::ThirdQuarter => "moon-third-quarter"
+    })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fluent_syntax::ast::Entry;
+
+    fn ids(source: &str) -> Vec<String> {
+        let resource = FluentResource::try_new(source.to_string())
+            .unwrap_or_else(|(_, errors)| panic!("invalid catalogue: {:?}", errors));
+
+        let mut ids: Vec<_> = resource.entries()
+            .filter_map(|entry| match entry {
+                Entry::Message(message) => Some(message.id.name.to_string()),
+                _ => None
+            })
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn catalogues_are_complete() {
+        let english = ids(ENGLISH);
+
+        for lang in Language::all() {
+            if let Some(source) = source(lang) {
+                let messages = ids(source);
+                let missing: Vec<_> = english.iter().filter(|k| !messages.contains(k)).collect();
+                let unknown: Vec<_> = messages.iter().filter(|k| !english.contains(k)).collect();
+
+                assert!(missing.is_empty(), "{} is missing {:?}", lang, missing);
+                assert!(unknown.is_empty(), "{} has unknown messages {:?}", lang, unknown);
+            }
+        }
+    }
+
+    #[test]
+    fn placeables() {
+        let locale = Locale::new(Some(Language::Spanish));
+        assert_eq!("Fase lunar", locale.get("moon-phase", None));
+        assert_eq!("missing-key", locale.get("missing-key", None));
+
+        assert_eq!(
+            "the profile `work` already exists",
+            format("error-profile-exists", &[("name", &"work")])
+        );
+    }
+
+    #[test]
+    fn fluent_syntax() {
+        let locale = Locale {
+            bundles: vec![bundle("en", concat!(
+                "calls = { $calls ->\n",
+                "    [one] one call\n",
+                "   *[other] { $calls } calls\n",
+                "}\n",
+                "help =\n",
+                "    first line\n",
+                "    second line\n",
+                "braces = {\"{\"} and {\"}\"}\n"
+            ))]
+        };
+
+        let mut args = FluentArgs::new();
+        args.set("calls", FluentValue::try_number("1"));
+        assert_eq!("one call", locale.get("calls", Some(&args)));
+        args.set("calls", FluentValue::try_number("3"));
+        assert_eq!("3 calls", locale.get("calls", Some(&args)));
+
+        assert_eq!("first line\nsecond line", locale.get("help", None));
+        assert_eq!("{ and }", locale.get("braces", None));
+    }
+}
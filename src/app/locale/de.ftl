@@ -0,0 +1,173 @@
+# Headings
+location = Ort
+current = Aktuell
+forecast = Vorhersage
+profile = Profil
+
+# Current and forecast tables
+last-updated = Zuletzt aktualisiert
+temperature-celsius = Temperatur in Grad Celsius
+temperature-fahrenheit = Temperatur in Grad Fahrenheit
+is-day = Ist Tag
+humidity = Luftfeuchtigkeit in Prozent
+cloud-cover = Bewölkung in Prozent
+air-quality = Luftqualität
+date = Datum
+day = Tag
+astro = Astronomie
+sunrise = Sonnenaufgang
+sunset = Sonnenuntergang
+moonrise = Mondaufgang
+moonset = Monduntergang
+moon-phase = Mondphase
+moon-illumination = Mondbeleuchtung in %
+yes = Ja
+no = Nein
+
+# Moon phases
+moon-new-moon = Neumond
+moon-waxing-crescent = Zunehmende Sichel
+moon-first-quarter = Erstes Viertel
+moon-waxing-gibbous = Zunehmender Mond
+moon-full-moon = Vollmond
+moon-waning-gibbous = Abnehmender Mond
+moon-last-quarter = Letztes Viertel
+moon-waning-crescent = Abnehmende Sichel
+moon-third-quarter = Letztes Viertel
+
+# Location table
+name = Name
+country = Land
+coords = Breiten- und Längengrad
+tz-id = Zeitzone
+localtime = Ortszeit
+
+# Air quality table
+carbon-monoxide = Kohlenmonoxid (μg/m3)
+ozone = Ozon (μg/m3)
+nitrogen-dioxide = Stickstoffdioxid (μg/m3)
+sulphur-dioxide = Schwefeldioxid (μg/m3)
+pm2-5 = PM2,5 (μg/m3)
+pm10 = PM10 (μg/m3)
+us-epa-index = US-EPA-Standard
+aqi-low = Niedrig
+aqi-moderate = Mäßig
+aqi-high = Hoch
+aqi-very-high = Sehr hoch
+
+# Languages table
+code = Code
+native-name = Eigenbezeichnung
+
+# Comparison table
+temperature = Temperatur
+condition = Wetterlage
+rain-chance = Regenwahrscheinlichkeit
+precipitation = Niederschlag
+wind = Wind
+aqi = Luftqualitätsindex
+error = Fehler
+error-several-locations = mehrere Orte können nur mit `now` und `forecast` verglichen werden
+
+# Charts
+uv-index = UV-Index
+
+# Watch
+next-refresh = Nächste Aktualisierung in
+stale = Veraltet, zuletzt aktualisiert um
+quit-hint = Drücke q zum Beenden
+
+# Dashboard
+feels-like = Gefühlt
+alerts = Warnungen
+no-alerts = Keine Warnungen
+hourly-temperature = Stündliche Temperatur
+hourly-precipitation = Stündlicher Niederschlag
+loading = Lädt…
+dashboard-help = ←/→ Ort · ↑/↓ Tag · u Einheiten · r aktualisieren · q beenden
+
+# Notifications
+notify-matched = { $rule }: { $value } um { $time }
+
+# Config
+unset = nicht gesetzt
+inherited = (geerbt)
+api-key-valid = Der API-Schlüssel ist gültig
+
+# Quota
+quota-key = API-Schlüssel
+quota-calls = Aufrufe
+quota-no-budget = kein Budget
+quota-period = Abrechnungszeitraum seit
+quota-reset = Nächste Zurücksetzung
+quota-soft-limit = Weiches Limit
+quota-rate-limit = Ratenlimit
+quota-per-minute = { $calls } Aufrufe pro Minute
+
+# Stats
+stats = Statistik
+stats-period = Zeitraum
+stats-days = Tage
+stats-mean-temp = Mittlere Temperatur
+stats-min-temp = Tiefsttemperatur
+stats-max-temp = Höchsttemperatur
+stats-rainy-days = Regentage
+stats-mean-humidity = Mittlere Luftfeuchtigkeit
+stats-max-gust = Stärkste Böe
+stats-daylight = Tageslicht
+stats-sunny-hours = Sonnenstunden
+
+# Degree days
+degree-days = Gradtage
+degree-days-hdd = HGT
+degree-days-cdd = KGT
+degree-days-cumulative-hdd = Kumulierte HGT
+degree-days-cumulative-cdd = Kumulierte KGT
+
+# Agriculture
+agro = Landwirtschaft
+agro-gdd = Wachstumsgradtage
+agro-cumulative-gdd = Kumulierte Wachstumsgradtage
+agro-et0 = ET0
+agro-cumulative-et0 = Kumulierte ET0
+agro-water-balance = Wasserbilanz
+
+# Errors
+error-no-api-key = kein API-Schlüssel konfiguriert, setze { $env }, verwende --api-key-file <Pfad> oder führe `weer config set api_key <Schlüssel>` aus
+error-api-key-file = die API-Schlüsseldatei { $path } konnte nicht gelesen werden: { $error }
+error-api-key-file-empty = die API-Schlüsseldatei { $path } ist leer
+error-api-key-rejected = Der API-Schlüssel wurde abgelehnt ({ $code }): { $message }
+error-api-key-chars = ein API-Schlüssel besteht nur aus Buchstaben und Ziffern
+error-unknown-key = unbekannter Konfigurationsschlüssel `{ $key }`, erwartet wird einer von: { $keys }
+error-key-not-set = `{ $key }` ist nicht gesetzt
+error-unknown-language = unbekannte Sprache `{ $lang }`, `weer languages` listet alle auf
+error-unknown-units = unbekannte Einheiten `{ $units }`, erwartet wird `metric` oder `imperial`
+error-empty-location = der Ort darf nicht leer sein
+error-expected-bool = `true` oder `false` erwartet, `{ $value }` erhalten
+error-expected-minutes = Anzahl an Minuten erwartet, `{ $value }` erhalten
+error-unknown-profile = unbekanntes Profil `{ $name }`, erstelle es mit `weer profile create { $name }`
+error-profile-name = ungültiger Profilname `{ $name }`, erlaubt sind Buchstaben, Ziffern, `-` und `_`
+error-profile-exists = das Profil `{ $name }` existiert bereits
+error-delete-default-profile = das Standardprofil kann nicht gelöscht werden
+error-invalid-hour = ungültige Stunde
+error-watch-interval = das Aktualisierungsintervall darf nicht kürzer als { $min } Minuten sein
+error-chart-several-locations = --chart nimmt nur einen Ort
+error-format-several-locations = --format und --preset nehmen nur einen Ort
+error-rule = ungültige Regel `{ $rule }`, z. B. `chance_of_rain > 60 within 3h`, `temp_c < 0 tomorrow 06:00-09:00` oder `alert flood`
+error-rule-field = unbekanntes Feld `{ $field }`, erwartet wird eines von: { $fields }
+error-notify-command = der Befehl ist mit { $status } fehlgeschlagen
+error-invalid-date = ungültiges Datum `{ $date }`, erwartet JJJJ-MM-TT: { $error }
+error-internal = interner Fehler, bitte melden: { $error }
+error-cassette = die Aufzeichnung { $path } konnte nicht gelesen werden: { $error }
+error-expected-calls = positive Anzahl von Aufrufen erwartet, erhalten `{ $value }`
+error-billing-day = Tag des Monats zwischen 1 und 31 erwartet, erhalten `{ $value }`
+error-percent = Prozentsatz zwischen 1 und 100 erwartet, erhalten `{ $value }`
+error-quota-action = unbekannte Kontingentaktion `{ $value }`, erwartet `refuse` oder `cache-only`
+error-quota-exceeded = das weiche Limit des Kontingents ist erreicht: { $calls } Aufrufe seit { $since }, Zurücksetzung am { $reset }
+error-no-data-dir = kein Datenverzeichnis für diesen Benutzer gefunden
+error-expected-seconds = positive Anzahl von Sekunden erwartet, erhalten `{ $value }`
+error-proxy = ungültiger Proxy `{ $proxy }`: { $error }
+error-root-certificates = die Stammzertifikate { $path } konnten nicht gelesen werden: { $error }
+error-client = die Verbindung konnte nicht eingerichtet werden: { $error }
+error-date-range = --end_dt darf nicht vor --dt liegen
+error-from-to = --to darf nicht vor --from liegen
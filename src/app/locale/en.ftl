@@ -0,0 +1,173 @@
+# Headings
+location = Location
+current = Current
+forecast = Forecast
+profile = Profile
+
+# Current and forecast tables
+last-updated = Last updated
+temperature-celsius = Temperature in celsius
+temperature-fahrenheit = Temperature in fahrenheit
+is-day = Is day
+humidity = Humidity as percentage
+cloud-cover = Cloud cover as percentage
+air-quality = Air quality
+date = Date
+day = Day
+astro = Astro
+sunrise = Sunrise time
+sunset = Sunset time
+moonrise = Moonrise time
+moonset = Moonset time
+moon-phase = Moon phase
+moon-illumination = Moon illumination as %
+yes = Yes
+no = No
+
+# Moon phases
+moon-new-moon = New Moon
+moon-waxing-crescent = Waxing Crescent
+moon-first-quarter = First Quarter
+moon-waxing-gibbous = Waxing Gibbous
+moon-full-moon = Full Moon
+moon-waning-gibbous = Waning Gibbous
+moon-last-quarter = Last Quarter
+moon-waning-crescent = Waning Crescent
+moon-third-quarter = Third Quarter
+
+# Location table
+name = Name
+country = Country
+coords = Latitude and Longitude
+tz-id = TimeZone ID
+localtime = Localtime
+
+# Air quality table
+carbon-monoxide = Carbon Monoxide (μg/m3)
+ozone = Ozone (μg/m3)
+nitrogen-dioxide = Nitrogen dioxide (μg/m3)
+sulphur-dioxide = Sulphur dioxide (μg/m3)
+pm2-5 = PM2.5 (μg/m3)
+pm10 = PM10 (μg/m3)
+us-epa-index = US - EPA standard
+aqi-low = Low
+aqi-moderate = Moderate
+aqi-high = High
+aqi-very-high = Very High
+
+# Languages table
+code = Code
+native-name = Native name
+
+# Comparison table
+temperature = Temperature
+condition = Condition
+rain-chance = Chance of rain
+precipitation = Precipitation
+wind = Wind
+aqi = Air quality index
+error = Error
+error-several-locations = several locations can only be compared with `now` and `forecast`
+
+# Charts
+uv-index = UV index
+
+# Watch
+next-refresh = Next refresh in
+stale = Stale, last updated at
+quit-hint = Press q to quit
+
+# Dashboard
+feels-like = Feels like
+alerts = Alerts
+no-alerts = No alerts
+hourly-temperature = Hourly temperature
+hourly-precipitation = Hourly precipitation
+loading = Loading…
+dashboard-help = ←/→ location · ↑/↓ day · u units · r refresh · q quit
+
+# Notifications
+notify-matched = { $rule }: { $value } at { $time }
+
+# Config
+unset = unset
+inherited = (inherited)
+api-key-valid = The api key is valid
+
+# Quota
+quota-key = Api key
+quota-calls = Calls
+quota-no-budget = no budget
+quota-period = Billing period since
+quota-reset = Next reset
+quota-soft-limit = Soft limit
+quota-rate-limit = Rate limit
+quota-per-minute = { $calls } calls per minute
+
+# Stats
+stats = Statistics
+stats-period = Period
+stats-days = Days
+stats-mean-temp = Mean temperature
+stats-min-temp = Minimum temperature
+stats-max-temp = Maximum temperature
+stats-rainy-days = Rainy days
+stats-mean-humidity = Mean humidity
+stats-max-gust = Maximum gust
+stats-daylight = Daylight
+stats-sunny-hours = Sunny hours
+
+# Degree days
+degree-days = Degree days
+degree-days-hdd = HDD
+degree-days-cdd = CDD
+degree-days-cumulative-hdd = Cumulative HDD
+degree-days-cumulative-cdd = Cumulative CDD
+
+# Agriculture
+agro = Agriculture
+agro-gdd = GDD
+agro-cumulative-gdd = Cumulative GDD
+agro-et0 = ET0
+agro-cumulative-et0 = Cumulative ET0
+agro-water-balance = Water balance
+
+# Errors
+error-no-api-key = no api key configured, set { $env } or use --api-key-file <path> or run `weer config set api_key <key>`
+error-api-key-file = could not read api key file { $path }: { $error }
+error-api-key-file-empty = api key file { $path } is empty
+error-api-key-rejected = The api key was rejected ({ $code }): { $message }
+error-api-key-chars = an api key is made only of letters and digits
+error-unknown-key = unknown config key `{ $key }`, expected one of: { $keys }
+error-key-not-set = `{ $key }` is not set
+error-unknown-language = unknown language `{ $lang }`, run `weer languages` to list them
+error-unknown-units = unknown units `{ $units }`, expected `metric` or `imperial`
+error-empty-location = the location can't be empty
+error-expected-bool = expected `true` or `false`, got `{ $value }`
+error-expected-minutes = expected a number of minutes, got `{ $value }`
+error-unknown-profile = unknown profile `{ $name }`, create it with `weer profile create { $name }`
+error-profile-name = invalid profile name `{ $name }`, use letters, digits, `-` and `_`
+error-profile-exists = the profile `{ $name }` already exists
+error-delete-default-profile = the default profile can't be deleted
+error-invalid-hour = invalid hour
+error-watch-interval = the watch interval can't be shorter than { $min } minutes
+error-chart-several-locations = --chart takes a single location
+error-format-several-locations = --format and --preset take a single location
+error-rule = invalid rule `{ $rule }`, expected e.g. `chance_of_rain > 60 within 3h`, `temp_c < 0 tomorrow 06:00-09:00` or `alert flood`
+error-rule-field = unknown field `{ $field }`, expected one of: { $fields }
+error-notify-command = the command failed with { $status }
+error-invalid-date = invalid date `{ $date }`, expected yyyy-mm-dd: { $error }
+error-internal = internal error, please report it: { $error }
+error-cassette = could not read the cassette { $path }: { $error }
+error-expected-calls = expected a positive number of calls, got `{ $value }`
+error-billing-day = expected a day of the month between 1 and 31, got `{ $value }`
+error-percent = expected a percentage between 1 and 100, got `{ $value }`
+error-quota-action = unknown quota action `{ $value }`, expected `refuse` or `cache-only`
+error-quota-exceeded = the soft limit of the quota is reached: { $calls } calls since { $since }, it resets on { $reset }
+error-no-data-dir = no data directory found for this user
+error-expected-seconds = expected a positive number of seconds, got `{ $value }`
+error-proxy = invalid proxy `{ $proxy }`: { $error }
+error-root-certificates = could not read the root certificates { $path }: { $error }
+error-client = could not set up the connection: { $error }
+error-date-range = --end_dt must not be before --dt
+error-from-to = --to must not be before --from
@@ -0,0 +1,173 @@
+# Headings
+location = Ubicación
+current = Actual
+forecast = Pronóstico
+profile = Perfil
+
+# Current and forecast tables
+last-updated = Última actualización
+temperature-celsius = Temperatura en grados Celsius
+temperature-fahrenheit = Temperatura en grados Fahrenheit
+is-day = Es de día
+humidity = Humedad en porcentaje
+cloud-cover = Nubosidad en porcentaje
+air-quality = Calidad del aire
+date = Fecha
+day = Día
+astro = Astronomía
+sunrise = Hora de salida del sol
+sunset = Hora de puesta del sol
+moonrise = Hora de salida de la luna
+moonset = Hora de puesta de la luna
+moon-phase = Fase lunar
+moon-illumination = Iluminación de la luna en %
+yes = Sí
+no = No
+
+# Moon phases
+moon-new-moon = Luna nueva
+moon-waxing-crescent = Luna creciente
+moon-first-quarter = Cuarto creciente
+moon-waxing-gibbous = Gibosa creciente
+moon-full-moon = Luna llena
+moon-waning-gibbous = Gibosa menguante
+moon-last-quarter = Cuarto menguante
+moon-waning-crescent = Luna menguante
+moon-third-quarter = Cuarto menguante
+
+# Location table
+name = Nombre
+country = País
+coords = Latitud y longitud
+tz-id = Zona horaria
+localtime = Hora local
+
+# Air quality table
+carbon-monoxide = Monóxido de carbono (μg/m3)
+ozone = Ozono (μg/m3)
+nitrogen-dioxide = Dióxido de nitrógeno (μg/m3)
+sulphur-dioxide = Dióxido de azufre (μg/m3)
+pm2-5 = PM2.5 (μg/m3)
+pm10 = PM10 (μg/m3)
+us-epa-index = Estándar EPA de EE. UU.
+aqi-low = Bajo
+aqi-moderate = Moderado
+aqi-high = Alto
+aqi-very-high = Muy alto
+
+# Languages table
+code = Código
+native-name = Nombre nativo
+
+# Comparison table
+temperature = Temperatura
+condition = Condición
+rain-chance = Probabilidad de lluvia
+precipitation = Precipitación
+wind = Viento
+aqi = Índice de calidad del aire
+error = Error
+error-several-locations = solo se pueden comparar varias ubicaciones con `now` y `forecast`
+
+# Charts
+uv-index = Índice UV
+
+# Watch
+next-refresh = Próxima actualización en
+stale = Desactualizado, última actualización a las
+quit-hint = Pulsa q para salir
+
+# Dashboard
+feels-like = Sensación térmica
+alerts = Alertas
+no-alerts = Sin alertas
+hourly-temperature = Temperatura por hora
+hourly-precipitation = Precipitación por hora
+loading = Cargando…
+dashboard-help = ←/→ ubicación · ↑/↓ día · u unidades · r actualizar · q salir
+
+# Notifications
+notify-matched = { $rule }: { $value } a las { $time }
+
+# Config
+unset = sin definir
+inherited = (heredado)
+api-key-valid = La clave de la API es válida
+
+# Quota
+quota-key = Clave de api
+quota-calls = Llamadas
+quota-no-budget = sin presupuesto
+quota-period = Periodo de facturación desde
+quota-reset = Próximo reinicio
+quota-soft-limit = Límite suave
+quota-rate-limit = Límite de frecuencia
+quota-per-minute = { $calls } llamadas por minuto
+
+# Stats
+stats = Estadísticas
+stats-period = Periodo
+stats-days = Días
+stats-mean-temp = Temperatura media
+stats-min-temp = Temperatura mínima
+stats-max-temp = Temperatura máxima
+stats-rainy-days = Días de lluvia
+stats-mean-humidity = Humedad media
+stats-max-gust = Racha máxima
+stats-daylight = Luz diurna
+stats-sunny-hours = Horas de sol
+
+# Degree days
+degree-days = Grados-día
+degree-days-hdd = GDC
+degree-days-cdd = GDR
+degree-days-cumulative-hdd = GDC acumulados
+degree-days-cumulative-cdd = GDR acumulados
+
+# Agriculture
+agro = Agricultura
+agro-gdd = GDC
+agro-cumulative-gdd = GDC acumulados
+agro-et0 = ET0
+agro-cumulative-et0 = ET0 acumulada
+agro-water-balance = Balance hídrico
+
+# Errors
+error-no-api-key = no hay ninguna clave de la API configurada, define { $env }, usa --api-key-file <ruta> o ejecuta `weer config set api_key <clave>`
+error-api-key-file = no se pudo leer el archivo de la clave de la API { $path }: { $error }
+error-api-key-file-empty = el archivo de la clave de la API { $path } está vacío
+error-api-key-rejected = La clave de la API fue rechazada ({ $code }): { $message }
+error-api-key-chars = una clave de la API solo contiene letras y dígitos
+error-unknown-key = clave de configuración `{ $key }` desconocida, se esperaba una de: { $keys }
+error-key-not-set = `{ $key }` no está definida
+error-unknown-language = idioma `{ $lang }` desconocido, ejecuta `weer languages` para listarlos
+error-unknown-units = unidades `{ $units }` desconocidas, se esperaba `metric` o `imperial`
+error-empty-location = la ubicación no puede estar vacía
+error-expected-bool = se esperaba `true` o `false`, se obtuvo `{ $value }`
+error-expected-minutes = se esperaba un número de minutos, se obtuvo `{ $value }`
+error-unknown-profile = perfil `{ $name }` desconocido, créalo con `weer profile create { $name }`
+error-profile-name = nombre de perfil `{ $name }` no válido, usa letras, dígitos, `-` y `_`
+error-profile-exists = el perfil `{ $name }` ya existe
+error-delete-default-profile = el perfil predeterminado no se puede eliminar
+error-invalid-hour = hora no válida
+error-watch-interval = el intervalo de actualización no puede ser menor de { $min } minutos
+error-chart-several-locations = --chart admite una sola ubicación
+error-format-several-locations = --format y --preset admiten una sola ubicación
+error-rule = regla no válida `{ $rule }`, por ejemplo `chance_of_rain > 60 within 3h`, `temp_c < 0 tomorrow 06:00-09:00` o `alert flood`
+error-rule-field = campo desconocido `{ $field }`, se esperaba uno de: { $fields }
+error-notify-command = el comando falló con { $status }
+error-invalid-date = fecha no válida `{ $date }`, se esperaba aaaa-mm-dd: { $error }
+error-internal = error interno, por favor infórmalo: { $error }
+error-cassette = no se pudo leer la grabación { $path }: { $error }
+error-expected-calls = se esperaba un número positivo de llamadas, se obtuvo `{ $value }`
+error-billing-day = se esperaba un día del mes entre 1 y 31, se obtuvo `{ $value }`
+error-percent = se esperaba un porcentaje entre 1 y 100, se obtuvo `{ $value }`
+error-quota-action = acción de cuota desconocida `{ $value }`, se esperaba `refuse` o `cache-only`
+error-quota-exceeded = se alcanzó el límite suave de la cuota: { $calls } llamadas desde el { $since }, se reinicia el { $reset }
+error-no-data-dir = no se encontró un directorio de datos para este usuario
+error-expected-seconds = se esperaba un número positivo de segundos, se obtuvo `{ $value }`
+error-proxy = proxy no válido `{ $proxy }`: { $error }
+error-root-certificates = no se pudieron leer los certificados raíz { $path }: { $error }
+error-client = no se pudo configurar la conexión: { $error }
+error-date-range = --end_dt no puede ser anterior a --dt
+error-from-to = --to no puede ser anterior a --from
@@ -0,0 +1,173 @@
+# Headings
+location = Lieu
+current = Actuellement
+forecast = Prévisions
+profile = Profil
+
+# Current and forecast tables
+last-updated = Dernière mise à jour
+temperature-celsius = Température en degrés Celsius
+temperature-fahrenheit = Température en degrés Fahrenheit
+is-day = Il fait jour
+humidity = Humidité en pourcentage
+cloud-cover = Couverture nuageuse en pourcentage
+air-quality = Qualité de l'air
+date = Date
+day = Jour
+astro = Astronomie
+sunrise = Lever du soleil
+sunset = Coucher du soleil
+moonrise = Lever de la lune
+moonset = Coucher de la lune
+moon-phase = Phase de la lune
+moon-illumination = Illumination de la lune en %
+yes = Oui
+no = Non
+
+# Moon phases
+moon-new-moon = Nouvelle lune
+moon-waxing-crescent = Premier croissant
+moon-first-quarter = Premier quartier
+moon-waxing-gibbous = Gibbeuse croissante
+moon-full-moon = Pleine lune
+moon-waning-gibbous = Gibbeuse décroissante
+moon-last-quarter = Dernier quartier
+moon-waning-crescent = Dernier croissant
+moon-third-quarter = Dernier quartier
+
+# Location table
+name = Nom
+country = Pays
+coords = Latitude et longitude
+tz-id = Fuseau horaire
+localtime = Heure locale
+
+# Air quality table
+carbon-monoxide = Monoxyde de carbone (μg/m3)
+ozone = Ozone (μg/m3)
+nitrogen-dioxide = Dioxyde d'azote (μg/m3)
+sulphur-dioxide = Dioxyde de soufre (μg/m3)
+pm2-5 = PM2,5 (μg/m3)
+pm10 = PM10 (μg/m3)
+us-epa-index = Norme EPA (États-Unis)
+aqi-low = Faible
+aqi-moderate = Modéré
+aqi-high = Élevé
+aqi-very-high = Très élevé
+
+# Languages table
+code = Code
+native-name = Nom natif
+
+# Comparison table
+temperature = Température
+condition = Conditions
+rain-chance = Risque de pluie
+precipitation = Précipitations
+wind = Vent
+aqi = Indice de qualité de l'air
+error = Erreur
+error-several-locations = plusieurs lieux ne peuvent être comparés qu'avec `now` et `forecast`
+
+# Charts
+uv-index = Indice UV
+
+# Watch
+next-refresh = Prochaine actualisation dans
+stale = Obsolète, dernière mise à jour à
+quit-hint = Appuyez sur q pour quitter
+
+# Dashboard
+feels-like = Ressenti
+alerts = Alertes
+no-alerts = Aucune alerte
+hourly-temperature = Température horaire
+hourly-precipitation = Précipitations horaires
+loading = Chargement…
+dashboard-help = ←/→ lieu · ↑/↓ jour · u unités · r actualiser · q quitter
+
+# Notifications
+notify-matched = { $rule } : { $value } à { $time }
+
+# Config
+unset = non défini
+inherited = (hérité)
+api-key-valid = La clé d'API est valide
+
+# Quota
+quota-key = Clé d'api
+quota-calls = Appels
+quota-no-budget = sans budget
+quota-period = Période de facturation depuis
+quota-reset = Prochaine remise à zéro
+quota-soft-limit = Limite souple
+quota-rate-limit = Limite de débit
+quota-per-minute = { $calls } appels par minute
+
+# Stats
+stats = Statistiques
+stats-period = Période
+stats-days = Jours
+stats-mean-temp = Température moyenne
+stats-min-temp = Température minimale
+stats-max-temp = Température maximale
+stats-rainy-days = Jours de pluie
+stats-mean-humidity = Humidité moyenne
+stats-max-gust = Rafale maximale
+stats-daylight = Durée du jour
+stats-sunny-hours = Heures ensoleillées
+
+# Degree days
+degree-days = Degrés-jours
+degree-days-hdd = DJU chauffage
+degree-days-cdd = DJU climatisation
+degree-days-cumulative-hdd = DJU chauffage cumulés
+degree-days-cumulative-cdd = DJU climatisation cumulés
+
+# Agriculture
+agro = Agriculture
+agro-gdd = DJC
+agro-cumulative-gdd = DJC cumulés
+agro-et0 = ET0
+agro-cumulative-et0 = ET0 cumulée
+agro-water-balance = Bilan hydrique
+
+# Errors
+error-no-api-key = aucune clé d'API configurée, définissez { $env }, utilisez --api-key-file <chemin> ou lancez `weer config set api_key <clé>`
+error-api-key-file = impossible de lire le fichier de clé d'API { $path } : { $error }
+error-api-key-file-empty = le fichier de clé d'API { $path } est vide
+error-api-key-rejected = La clé d'API a été refusée ({ $code }) : { $message }
+error-api-key-chars = une clé d'API ne contient que des lettres et des chiffres
+error-unknown-key = clé de configuration `{ $key }` inconnue, valeurs possibles : { $keys }
+error-key-not-set = `{ $key }` n'est pas défini
+error-unknown-language = langue `{ $lang }` inconnue, lancez `weer languages` pour les lister
+error-unknown-units = unités `{ $units }` inconnues, valeurs possibles : `metric` ou `imperial`
+error-empty-location = le lieu ne peut pas être vide
+error-expected-bool = `true` ou `false` attendu, `{ $value }` reçu
+error-expected-minutes = nombre de minutes attendu, `{ $value }` reçu
+error-unknown-profile = profil `{ $name }` inconnu, créez-le avec `weer profile create { $name }`
+error-profile-name = nom de profil `{ $name }` invalide, utilisez des lettres, des chiffres, `-` et `_`
+error-profile-exists = le profil `{ $name }` existe déjà
+error-delete-default-profile = le profil par défaut ne peut pas être supprimé
+error-invalid-hour = heure invalide
+error-watch-interval = l'intervalle d'actualisation ne peut pas être inférieur à { $min } minutes
+error-chart-several-locations = --chart ne prend qu'un seul lieu
+error-format-several-locations = --format et --preset ne prennent qu'un seul lieu
+error-rule = règle invalide `{ $rule }`, par exemple `chance_of_rain > 60 within 3h`, `temp_c < 0 tomorrow 06:00-09:00` ou `alert flood`
+error-rule-field = champ inconnu `{ $field }`, attendu l'un de : { $fields }
+error-notify-command = la commande a échoué avec { $status }
+error-invalid-date = date invalide `{ $date }`, format attendu aaaa-mm-jj : { $error }
+error-internal = erreur interne, merci de la signaler : { $error }
+error-cassette = impossible de lire l'enregistrement { $path } : { $error }
+error-expected-calls = nombre d'appels positif attendu, reçu `{ $value }`
+error-billing-day = jour du mois entre 1 et 31 attendu, reçu `{ $value }`
+error-percent = pourcentage entre 1 et 100 attendu, reçu `{ $value }`
+error-quota-action = action de quota inconnue `{ $value }`, attendu `refuse` ou `cache-only`
+error-quota-exceeded = la limite souple du quota est atteinte : { $calls } appels depuis le { $since }, remise à zéro le { $reset }
+error-no-data-dir = aucun répertoire de données trouvé pour cet utilisateur
+error-expected-seconds = nombre de secondes positif attendu, reçu `{ $value }`
+error-proxy = proxy invalide `{ $proxy }` : { $error }
+error-root-certificates = impossible de lire les certificats racine { $path } : { $error }
+error-client = impossible de configurer la connexion : { $error }
+error-date-range = --end_dt ne peut pas précéder --dt
+error-from-to = --to ne peut pas précéder --from
@@ -0,0 +1,173 @@
+# Headings
+location = Localização
+current = Atual
+forecast = Previsão
+profile = Perfil
+
+# Current and forecast tables
+last-updated = Última atualização
+temperature-celsius = Temperatura em graus Celsius
+temperature-fahrenheit = Temperatura em graus Fahrenheit
+is-day = É dia
+humidity = Umidade em porcentagem
+cloud-cover = Cobertura de nuvens em porcentagem
+air-quality = Qualidade do ar
+date = Data
+day = Dia
+astro = Astronomia
+sunrise = Nascer do sol
+sunset = Pôr do sol
+moonrise = Nascer da lua
+moonset = Pôr da lua
+moon-phase = Fase da lua
+moon-illumination = Iluminação da lua em %
+yes = Sim
+no = Não
+
+# Moon phases
+moon-new-moon = Lua nova
+moon-waxing-crescent = Lua crescente
+moon-first-quarter = Quarto crescente
+moon-waxing-gibbous = Crescente gibosa
+moon-full-moon = Lua cheia
+moon-waning-gibbous = Minguante gibosa
+moon-last-quarter = Quarto minguante
+moon-waning-crescent = Lua minguante
+moon-third-quarter = Quarto minguante
+
+# Location table
+name = Nome
+country = País
+coords = Latitude e longitude
+tz-id = Fuso horário
+localtime = Hora local
+
+# Air quality table
+carbon-monoxide = Monóxido de carbono (μg/m3)
+ozone = Ozônio (μg/m3)
+nitrogen-dioxide = Dióxido de nitrogênio (μg/m3)
+sulphur-dioxide = Dióxido de enxofre (μg/m3)
+pm2-5 = PM2,5 (μg/m3)
+pm10 = PM10 (μg/m3)
+us-epa-index = Padrão EPA dos EUA
+aqi-low = Baixo
+aqi-moderate = Moderado
+aqi-high = Alto
+aqi-very-high = Muito alto
+
+# Languages table
+code = Código
+native-name = Nome nativo
+
+# Comparison table
+temperature = Temperatura
+condition = Condição
+rain-chance = Chance de chuva
+precipitation = Precipitação
+wind = Vento
+aqi = Índice de qualidade do ar
+error = Erro
+error-several-locations = várias localizações só podem ser comparadas com `now` e `forecast`
+
+# Charts
+uv-index = Índice UV
+
+# Watch
+next-refresh = Próxima atualização em
+stale = Desatualizado, última atualização às
+quit-hint = Pressione q para sair
+
+# Dashboard
+feels-like = Sensação térmica
+alerts = Alertas
+no-alerts = Sem alertas
+hourly-temperature = Temperatura por hora
+hourly-precipitation = Precipitação por hora
+loading = Carregando…
+dashboard-help = ←/→ local · ↑/↓ dia · u unidades · r atualizar · q sair
+
+# Notifications
+notify-matched = { $rule }: { $value } às { $time }
+
+# Config
+unset = não definido
+inherited = (herdado)
+api-key-valid = A chave da API é válida
+
+# Quota
+quota-key = Chave de api
+quota-calls = Chamadas
+quota-no-budget = sem orçamento
+quota-period = Período de faturação desde
+quota-reset = Próxima reposição
+quota-soft-limit = Limite flexível
+quota-rate-limit = Limite de taxa
+quota-per-minute = { $calls } chamadas por minuto
+
+# Stats
+stats = Estatísticas
+stats-period = Período
+stats-days = Dias
+stats-mean-temp = Temperatura média
+stats-min-temp = Temperatura mínima
+stats-max-temp = Temperatura máxima
+stats-rainy-days = Dias de chuva
+stats-mean-humidity = Humidade média
+stats-max-gust = Rajada máxima
+stats-daylight = Luz do dia
+stats-sunny-hours = Horas de sol
+
+# Degree days
+degree-days = Graus-dia
+degree-days-hdd = GDA
+degree-days-cdd = GDR
+degree-days-cumulative-hdd = GDA acumulados
+degree-days-cumulative-cdd = GDR acumulados
+
+# Agriculture
+agro = Agricultura
+agro-gdd = GDC
+agro-cumulative-gdd = GDC acumulados
+agro-et0 = ET0
+agro-cumulative-et0 = ET0 acumulada
+agro-water-balance = Balanço hídrico
+
+# Errors
+error-no-api-key = nenhuma chave da API configurada, defina { $env }, use --api-key-file <caminho> ou execute `weer config set api_key <chave>`
+error-api-key-file = não foi possível ler o arquivo da chave da API { $path }: { $error }
+error-api-key-file-empty = o arquivo da chave da API { $path } está vazio
+error-api-key-rejected = A chave da API foi recusada ({ $code }): { $message }
+error-api-key-chars = uma chave da API contém apenas letras e dígitos
+error-unknown-key = chave de configuração `{ $key }` desconhecida, esperava-se uma de: { $keys }
+error-key-not-set = `{ $key }` não está definida
+error-unknown-language = idioma `{ $lang }` desconhecido, execute `weer languages` para listá-los
+error-unknown-units = unidades `{ $units }` desconhecidas, esperava-se `metric` ou `imperial`
+error-empty-location = a localização não pode ficar vazia
+error-expected-bool = esperava-se `true` ou `false`, recebido `{ $value }`
+error-expected-minutes = esperava-se um número de minutos, recebido `{ $value }`
+error-unknown-profile = perfil `{ $name }` desconhecido, crie-o com `weer profile create { $name }`
+error-profile-name = nome de perfil `{ $name }` inválido, use letras, dígitos, `-` e `_`
+error-profile-exists = o perfil `{ $name }` já existe
+error-delete-default-profile = o perfil padrão não pode ser excluído
+error-invalid-hour = hora inválida
+error-watch-interval = o intervalo de atualização não pode ser menor que { $min } minutos
+error-chart-several-locations = --chart aceita apenas um local
+error-format-several-locations = --format e --preset aceitam apenas um local
+error-rule = regra inválida `{ $rule }`, por exemplo `chance_of_rain > 60 within 3h`, `temp_c < 0 tomorrow 06:00-09:00` ou `alert flood`
+error-rule-field = campo desconhecido `{ $field }`, esperado um de: { $fields }
+error-notify-command = o comando falhou com { $status }
+error-invalid-date = data inválida `{ $date }`, esperado aaaa-mm-dd: { $error }
+error-internal = erro interno, por favor reporte-o: { $error }
+error-cassette = não foi possível ler a gravação { $path }: { $error }
+error-expected-calls = esperava-se um número positivo de chamadas, obteve-se `{ $value }`
+error-billing-day = esperava-se um dia do mês entre 1 e 31, obteve-se `{ $value }`
+error-percent = esperava-se uma percentagem entre 1 e 100, obteve-se `{ $value }`
+error-quota-action = ação de quota desconhecida `{ $value }`, esperava-se `refuse` ou `cache-only`
+error-quota-exceeded = o limite flexível da quota foi atingido: { $calls } chamadas desde { $since }, repõe em { $reset }
+error-no-data-dir = nenhum diretório de dados encontrado para este utilizador
+error-expected-seconds = esperava-se um número positivo de segundos, obteve-se `{ $value }`
+error-proxy = proxy inválido `{ $proxy }`: { $error }
+error-root-certificates = não foi possível ler os certificados raiz { $path }: { $error }
+error-client = não foi possível configurar a ligação: { $error }
+error-date-range = --end_dt não pode ser anterior a --dt
+error-from-to = --to não pode ser anterior a --from
@@ -0,0 +1,30 @@
+//! Request logs of `-v` and `-vv`, printed to stderr
+//!
+//! `-v` prints a line per request with its endpoint, parameters, status, latency and retries, or the cache or
+//! cassette that answered it. `-vv` adds every attempt, cache miss, rate limiter wait and quota check. The api key is
+//! redacted by `weer_api` before it reaches the logs.
+
+use std::io::{self, IsTerminal};
+use tracing::Level;
+use tracing_subscriber::EnvFilter;
+
+
+/// Sets up the logs for `verbosity` occurrences of `-v`, there are none without it
+pub fn init(verbosity: u8) {
+    let level = match verbosity {
+        0 => return,
+        1 => Level::INFO,
+        2 => Level::DEBUG,
+        _ => Level::TRACE
+    };
+
+    // Logs of the dependencies are left out
+    let filter = EnvFilter::new(format!("weer={level},weer_api={level}"));
+
+    // Only fails if a subscriber is set already
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .with_ansi(io::stderr().is_terminal())
+        .try_init();
+}
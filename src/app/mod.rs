@@ -1,51 +1,130 @@
+mod chart;
 mod cmd;
+mod completions;
+mod config;
+mod dashboard;
+mod error;
+mod format;
 mod handler;
 mod image;
+mod locale;
+mod log;
+mod notify;
 mod tables;
+mod watch;
 
 use clap::ArgMatches;
-use std::{error::Error};
-use serde::{Serialize, Deserialize};
-use ureq::{Agent, AgentBuilder};
-use weer_api::{Client, Language};
+use std::{error::Error, cell::OnceCell, io, path::PathBuf};
+use weer_api::{Cassette, Client};
 
-
-#[derive(Serialize, Deserialize, Clone)]
-pub struct Config {
-    lang: Option<Language>,
-    api_key: String
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self { lang: None, api_key: "b406c89026bf4209b5511231222906".to_string() }
-    }
-}
+pub use config::{Config, ConfigFile};
+pub use error::{catch_panics, panicked, report};
 
 
 pub struct App {
     pub matches: ArgMatches,
+    /// Every profile, as stored in the config file
+    pub file: ConfigFile,
+    /// Name of the selected profile
+    pub profile: String,
+    /// Effective settings of the selected profile
     pub cfg: Config,
-    pub client: Client,
-    pub agent: Agent
+    client: OnceCell<Client>
 }
 
 impl App {
-    pub fn new() -> Self {
-        let cmd = cmd::build();
-        let agent = AgentBuilder::new().build();
-        let cfg: Config = confy::load("weer", "config").unwrap();
-        let client = Client::new(&cfg.api_key, true);
-
-        Self {
-            matches: cmd.get_matches(),
+    pub fn new() -> Result<Self, Box<dyn Error>> {
+        let matches = match cmd::build().try_get_matches() {
+            Ok(matches) => matches,
+            // --help and --version
+            Err(err) if !err.use_stderr() => err.exit(),
+            Err(err) => return Err(error::new(error::Kind::Usage, err.to_string().trim_end()))
+        };
+        log::init(matches.get_count("verbose"));
+
+        let file = ConfigFile::load().map_err(error::config)?;
+        let profile = config::selected_profile(matches.get_one::<String>("profile"));
+
+        // Errors are worded in the language of the selected profile, or the default one if it doesn't exist
+        locale::init(file.profiles.get(&profile).map_or(file.default.lang(), |p| p.inherit(&file.default).lang()));
+
+        let cfg = match file.profile(&profile) {
+            Ok(cfg) => cfg,
+            // Managing profiles must work while the selected one doesn't exist yet
+            Err(_) if matches.subcommand_name() == Some("profile") => file.default.clone(),
+            Err(err) => return Err(error::config(err))
+        };
+
+        Ok(Self {
+            matches,
+            file,
+            profile,
             cfg,
-            client,
-            agent
+            client: OnceCell::new()
+        })
+    }
+
+    /// Client built from the resolved api key
+    ///
+    /// Commands that don't talk to the API never need a key, so it is only resolved here.
+    pub fn client(&self) -> Result<&Client, Box<dyn Error>> {
+        if let Some(client) = self.client.get() {
+            return Ok(client);
         }
+
+        let client = self.build_client(true)?;
+        Ok(self.client.get_or_init(|| client))
+    }
+
+    /// Client of [`App::client`] without the response cache, for requests that must reach the API
+    ///
+    /// Its calls still go through the quota and the rate limiter.
+    pub fn uncached_client(&self) -> Result<Client, Box<dyn Error>> {
+        self.build_client(false)
+    }
+
+    fn build_client(&self, cached: bool) -> Result<Client, Box<dyn Error>> {
+        let replay = self.matches.get_one::<PathBuf>("replay");
+        // A replay never reaches the API
+        let api_key = match replay {
+            Some(_) => self.api_key().unwrap_or_default(),
+            None => self.api_key()?
+        };
+
+        let mut client = self.cfg.client(&api_key).map_err(error::config)?;
+        if let Some(cache) = self.cfg.cache().filter(|_| cached) {
+            client.cache(cache);
+        }
+        if let Some(quota) = self.cfg.quota() {
+            client.quota(quota);
+        }
+        if let Some(limiter) = self.cfg.rate_limiter() {
+            client.rate_limit(limiter);
+        }
+        if let Some(path) = self.matches.get_one::<PathBuf>("record") {
+            client.cassette(Cassette::record(path));
+        }
+        if let Some(path) = replay {
+            let cassette = Cassette::replay(path).map_err(|err| {
+                let kind = match err.kind() {
+                    io::ErrorKind::NotFound => error::Kind::Usage,
+                    io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof => error::Kind::Data,
+                    _ => error::Kind::Io
+                };
+                error::new(kind, locale::format("error-cassette", &[("path", &path.display()), ("error", &err)]))
+            })?;
+            client.cassette(cassette);
+        }
+
+        Ok(client)
+    }
+
+    pub fn api_key(&self) -> Result<String, Box<dyn Error>> {
+        let key_file = self.matches.get_one::<PathBuf>("api-key-file");
+        config::resolve_api_key(&self.cfg, key_file.map(|p| p.as_path())).map_err(error::config)
     }
 
     pub fn run(&self) -> Result<(), Box<dyn Error>> {
-        handler::matches_handler(&self)
+        handler::matches_handler(self)
     }
 }
@@ -0,0 +1,505 @@
+//! Rules of `weer notify`
+//!
+//! A rule is a condition on an hourly field, or on the weather alerts, checked over a window of the forecast:
+//! `chance_of_rain > 60 within 3h`, `temp_c < 0 tomorrow 06:00-09:00` or `alert flood`. Rules without a window
+//! look at the next 24 hours.
+//!
+//! Events are remembered in a state file so that a rain spell or an alert is only reported once, however often
+//! `weer notify` runs.
+
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display},
+    fs,
+    path::PathBuf,
+    process,
+    str::FromStr
+};
+
+use serde::{Serialize, Deserialize};
+use weer_api::{
+    Forecast, Hour, WeatherAlert,
+    chrono::{DateTime, Duration, FixedOffset, Local, NaiveDateTime, NaiveTime, Timelike}
+};
+use super::{config, locale};
+
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Events older than this are forgotten
+const STATE_DAYS: i64 = 14;
+
+type Getter = fn(&Hour) -> f32;
+
+/// Hourly fields a rule can test
+const FIELDS: [(&str, Getter); 22] = [
+    ("temp_c", |h| h.temp_c),
+    ("temp_f", |h| h.temp_f),
+    ("feelslike_c", |h| h.feelslike_c),
+    ("feelslike_f", |h| h.feelslike_f),
+    ("windchill_c", |h| h.windchill_c),
+    ("windchill_f", |h| h.windchill_f),
+    ("heatindex_c", |h| h.heatindex_c),
+    ("heatindex_f", |h| h.heatindex_f),
+    ("dewpoint_c", |h| h.dewpoint_c),
+    ("dewpoint_f", |h| h.dewpoint_f),
+    ("chance_of_rain", |h| h.chance_of_rain as f32),
+    ("chance_of_snow", |h| h.chance_of_snow as f32),
+    ("precip_mm", |h| h.precip_mm),
+    ("precip_in", |h| h.precip_in),
+    ("wind_kph", |h| h.wind_kph),
+    ("wind_mph", |h| h.wind_mph),
+    ("gust_kph", |h| h.gust_kph),
+    ("gust_mph", |h| h.gust_mph),
+    ("humidity", |h| h.humidity as f32),
+    ("cloud", |h| h.cloud as f32),
+    ("uv", |h| h.uv),
+    ("vis_km", |h| h.vis_km)
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne
+}
+
+impl Op {
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "<" => Op::Lt,
+            "<=" => Op::Le,
+            ">" => Op::Gt,
+            ">=" => Op::Ge,
+            "==" | "=" => Op::Eq,
+            "!=" => Op::Ne,
+            _ => return None
+        })
+    }
+
+    fn test(&self, a: f32, b: f32) -> bool {
+        match self {
+            Op::Lt => a < b,
+            Op::Le => a <= b,
+            Op::Gt => a > b,
+            Op::Ge => a >= b,
+            Op::Eq => a == b,
+            Op::Ne => a != b
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    Field { field: usize, op: Op, value: f32 },
+    /// Any alert, or only the ones whose event or headline contains the text
+    Alert(Option<String>)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Window {
+    /// The next hours, the current one included
+    Within(u32),
+    Today,
+    Tomorrow
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    source: String,
+    condition: Condition,
+    window: Window,
+    /// Only the hours between these times, both included
+    hours: Option<(NaiveTime, NaiveTime)>
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || locale::format("error-rule", &[("rule", &s)]);
+        let mut tokens = s.split_whitespace().peekable();
+
+        let condition = match tokens.next().ok_or_else(invalid)? {
+            "alert" => {
+                let mut words = vec![];
+                while let Some(word) = tokens.next_if(|t| !is_window(t)) {
+                    words.push(word.to_lowercase());
+                }
+                Condition::Alert(Some(words.join(" ")).filter(|w| !w.is_empty()))
+            }
+            name => {
+                let field = FIELDS.iter().position(|(f, _)| *f == name).ok_or_else(|| {
+                    let fields: Vec<_> = FIELDS.iter().map(|(f, _)| *f).collect();
+                    locale::format("error-rule-field", &[("field", &name), ("fields", &fields.join(", "))])
+                })?;
+                let op = tokens.next().and_then(Op::parse).ok_or_else(invalid)?;
+                let value = tokens.next().and_then(|v| v.parse().ok()).ok_or_else(invalid)?;
+
+                Condition::Field { field, op, value }
+            }
+        };
+
+        let mut window = Window::Within(24);
+        let mut hours = None;
+
+        while let Some(token) = tokens.next() {
+            match token {
+                "today" => window = Window::Today,
+                "tomorrow" => window = Window::Tomorrow,
+                "within" => {
+                    window = tokens.next()
+                        .and_then(|t| t.strip_suffix('h'))
+                        .and_then(|n| n.parse().ok())
+                        .map(Window::Within)
+                        .ok_or_else(invalid)?
+                }
+                range => hours = Some(parse_hours(range).ok_or_else(invalid)?)
+            }
+        }
+
+        Ok(Self { source: s.trim().to_string(), condition, window, hours })
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+fn is_window(token: &str) -> bool {
+    matches!(token, "today" | "tomorrow" | "within") || parse_hours(token).is_some()
+}
+
+/// `06:00-09:00`
+fn parse_hours(s: &str) -> Option<(NaiveTime, NaiveTime)> {
+    let (start, end) = s.split_once('-')?;
+    Some((NaiveTime::parse_from_str(start, "%H:%M").ok()?, NaiveTime::parse_from_str(end, "%H:%M").ok()?))
+}
+
+impl Rule {
+    /// Days of forecast needed to cover the window, counting today
+    pub fn days(&self, now: NaiveDateTime) -> u8 {
+        match self.window {
+            Window::Today => 1,
+            Window::Tomorrow => 2,
+            Window::Within(hours) => ((now.hour() + hours) / 24 + 1).min(14) as u8
+        }
+    }
+
+    fn covers(&self, time: NaiveDateTime, now: NaiveDateTime) -> bool {
+        let in_window = match self.window {
+            Window::Today => time.date() == now.date(),
+            Window::Tomorrow => Some(time.date()) == now.date().succ_opt(),
+            Window::Within(hours) => {
+                let start = now.with_minute(0).unwrap_or(now);
+                time >= start && time <= now + Duration::hours(hours as i64)
+            }
+        };
+
+        in_window && self.hours.is_none_or(|(start, end)| time.time() >= start && time.time() <= end)
+    }
+
+    /// Events of the rule in `forecast`
+    ///
+    /// A field rule gives at most one event: its first matching hour, along with the last hour of that spell.
+    pub fn check(&self, forecast: &Forecast, now: NaiveDateTime) -> Vec<Event> {
+        let location = &forecast.location.name;
+
+        match &self.condition {
+            Condition::Field { field, op, value } => {
+                let (name, get) = FIELDS[*field];
+                let mut matching = forecast.forecast.forecast_day.iter()
+                    .flat_map(|fd| fd.hour.iter())
+                    .filter_map(|h| Some((NaiveDateTime::parse_from_str(&h.time, TIME_FORMAT).ok()?, h)))
+                    .filter(|(time, _)| self.covers(*time, now))
+                    .skip_while(|(_, h)| !op.test(get(h), *value));
+
+                let Some((start, first)) = matching.next() else { return vec![] };
+                let end = matching.take_while(|(_, h)| op.test(get(h), *value))
+                    .last()
+                    .map_or(start, |(time, _)| time);
+
+                vec![Event {
+                    key: format!("{}@{}", self.source, location),
+                    until: end,
+                    summary: locale::format("notify-matched", &[
+                        ("rule", &self.source),
+                        ("value", &get(first)),
+                        ("time", &first.time)
+                    ]),
+                    vars: vec![
+                        ("rule", self.source.clone()),
+                        ("location", location.clone()),
+                        ("time", first.time.clone()),
+                        ("until", end.format(TIME_FORMAT).to_string()),
+                        ("field", name.to_string()),
+                        ("value", get(first).to_string()),
+                        ("condition", first.condition.text.clone())
+                    ],
+                    start
+                }]
+            }
+            Condition::Alert(text) => {
+                let alerts = forecast.alerts.as_ref().map_or(&[][..], |a| a.alert.as_slice());
+
+                let offset = local_offset(forecast);
+
+                alerts.iter()
+                    .filter(|alert| text.as_ref().is_none_or(|text| {
+                        alert.event.to_lowercase().contains(text) || alert.head_line.to_lowercase().contains(text)
+                    }))
+                    .map(|alert| self.alert_event(alert, location, now, offset))
+                    .collect()
+            }
+        }
+    }
+
+    /// `offset` is the one of the location, to compare the expiry with `now`
+    fn alert_event(&self, alert: &WeatherAlert, location: &str, now: NaiveDateTime, offset: Option<FixedOffset>) -> Event {
+        let expires = alert.expires.parse::<DateTime<FixedOffset>>()
+            .map(|e| match offset {
+                Some(offset) => e.with_timezone(&offset).naive_local(),
+                None => e.with_timezone(&Local).naive_local()
+            })
+            .unwrap_or(now);
+
+        Event {
+            key: format!("{}@{}@{}@{}", self.source, location, alert.head_line, alert.effective),
+            start: now,
+            until: expires,
+            summary: alert.head_line.clone(),
+            vars: vec![
+                ("rule", self.source.clone()),
+                ("location", location.to_string()),
+                ("alert_event", alert.event.clone()),
+                ("alert_severity", alert.severity.clone()),
+                ("alert_headline", alert.head_line.clone()),
+                ("alert_effective", alert.effective.clone()),
+                ("alert_expires", alert.expires.clone())
+            ]
+        }
+    }
+}
+
+/// Something a rule matched
+pub struct Event {
+    /// Identity of the event in the state file
+    key: String,
+    start: NaiveDateTime,
+    /// Until when the same key is the same event
+    until: NaiveDateTime,
+    pub summary: String,
+    vars: Vec<(&'static str, String)>
+}
+
+impl Event {
+    /// Matched data as `WEER_*` variables
+    fn env(&self) -> impl Iterator<Item = (String, &String)> {
+        self.vars.iter().map(|(name, value)| (format!("WEER_{}", name.to_uppercase()), value))
+    }
+
+    fn json(&self) -> serde_json::Value {
+        let vars: serde_json::Map<_, _> = self.vars.iter()
+            .map(|(name, value)| (name.to_string(), value.clone().into()))
+            .chain([("summary".to_string(), self.summary.clone().into())])
+            .collect();
+
+        vars.into()
+    }
+}
+
+/// Events already reported, by key with the end of the event
+#[derive(Serialize, Deserialize, Default)]
+pub struct State {
+    seen: BTreeMap<String, String>
+}
+
+impl State {
+    fn path() -> Option<PathBuf> {
+        config::data_dir().map(|dir| dir.join("notify.json"))
+    }
+
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn store(&mut self, now: NaiveDateTime) -> Result<(), Box<dyn Error>> {
+        let oldest = now - Duration::days(STATE_DAYS);
+        self.seen.retain(|_, until| {
+            NaiveDateTime::parse_from_str(until, TIME_FORMAT).is_ok_and(|until| until >= oldest)
+        });
+
+        if let Some(path) = Self::path() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, serde_json::to_string_pretty(self)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether `event` is new, a spell that overlaps the one reported last isn't
+    pub fn is_new(&self, event: &Event) -> bool {
+        self.seen.get(&event.key)
+            .and_then(|until| NaiveDateTime::parse_from_str(until, TIME_FORMAT).ok())
+            .is_none_or(|until| event.start > until)
+    }
+
+    pub fn remember(&mut self, event: &Event) {
+        self.seen.insert(event.key.clone(), event.until.format(TIME_FORMAT).to_string());
+    }
+}
+
+/// What to do with an event
+pub enum Action {
+    Desktop,
+    Command(String),
+    Webhook(String)
+}
+
+impl Action {
+    pub fn run(&self, event: &Event) -> Result<(), Box<dyn Error>> {
+        match self {
+            Action::Desktop => {
+                notify_rust::Notification::new()
+                    .appname("weer")
+                    .summary("weer")
+                    .body(&event.summary)
+                    .show()?;
+            }
+            Action::Command(command) => {
+                let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+                let status = process::Command::new(shell)
+                    .args([flag, command])
+                    .envs(event.env())
+                    .env("WEER_SUMMARY", &event.summary)
+                    .status()?;
+
+                if !status.success() {
+                    return Err(locale::format("error-notify-command", &[("status", &status)]).into());
+                }
+            }
+            Action::Webhook(url) => {
+                ureq::post(url).send_json(event.json())?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Current time at the location, the clock of the forecast hours
+pub fn local_now(forecast: &Forecast) -> NaiveDateTime {
+    forecast.location.localtime.as_ref()
+        .and_then(|t| NaiveDateTime::parse_from_str(t, TIME_FORMAT).ok())
+        .unwrap_or_else(|| Local::now().naive_local())
+}
+
+/// Offset of the location from UTC, `None` when the forecast lacks its local time
+pub fn local_offset(forecast: &Forecast) -> Option<FixedOffset> {
+    let location = &forecast.location;
+    let local = NaiveDateTime::parse_from_str(location.localtime.as_ref()?, TIME_FORMAT).ok()?;
+    let utc = DateTime::from_timestamp(location.localtime_epoch?, 0)?.naive_utc();
+
+    // The local time has no seconds, every offset is a multiple of 15 minutes
+    let seconds = (local - utc).num_seconds();
+    FixedOffset::east_opt(((seconds + 450).div_euclid(900) * 900) as i32)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weer_api::mock::fixtures;
+
+    fn time(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, TIME_FORMAT).unwrap()
+    }
+
+    #[test]
+    fn parse_rules() {
+        let rule: Rule = "chance_of_rain > 60 within 3h".parse().unwrap();
+        assert_eq!(Condition::Field { field: 10, op: Op::Gt, value: 60.0 }, rule.condition);
+        assert_eq!(Window::Within(3), rule.window);
+        assert_eq!(None, rule.hours);
+
+        let rule: Rule = "temp_c < 0 tomorrow 06:00-09:00".parse().unwrap();
+        assert_eq!(Window::Tomorrow, rule.window);
+        assert_eq!(Some((NaiveTime::from_hms_opt(6, 0, 0).unwrap(), NaiveTime::from_hms_opt(9, 0, 0).unwrap())), rule.hours);
+
+        let rule: Rule = "alert Flood Warning today".parse().unwrap();
+        assert_eq!(Condition::Alert(Some("flood warning".to_string())), rule.condition);
+        assert_eq!(Window::Today, rule.window);
+
+        assert_eq!(Condition::Alert(None), "alert".parse::<Rule>().unwrap().condition);
+
+        assert!("rain > 60".parse::<Rule>().is_err());
+        assert!("temp_c >> 0".parse::<Rule>().is_err());
+        assert!("temp_c < cold".parse::<Rule>().is_err());
+        assert!("temp_c < 0 within 3".parse::<Rule>().is_err());
+        assert!("temp_c < 0 06:00".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn windows() {
+        let now = time("2023-10-19 22:30");
+
+        let rule: Rule = "temp_c < 0 within 3h".parse().unwrap();
+        assert_eq!(2, rule.days(now));
+        assert!(rule.covers(time("2023-10-19 22:00"), now));
+        assert!(rule.covers(time("2023-10-20 01:00"), now));
+        assert!(!rule.covers(time("2023-10-20 02:00"), now));
+        assert!(!rule.covers(time("2023-10-19 21:00"), now));
+
+        let rule: Rule = "temp_c < 0 tomorrow 06:00-09:00".parse().unwrap();
+        assert_eq!(2, rule.days(now));
+        assert!(rule.covers(time("2023-10-20 09:00"), now));
+        assert!(!rule.covers(time("2023-10-20 10:00"), now));
+        assert!(!rule.covers(time("2023-10-19 07:00"), now));
+    }
+
+    #[test]
+    fn forecast_events() {
+        let forecast: Forecast = serde_json::from_str(fixtures::FORECAST).unwrap();
+        let now = local_now(&forecast);
+        assert_eq!(time("2023-10-19 14:00"), now);
+
+        let events = "chance_of_rain > 60 within 12h".parse::<Rule>().unwrap().check(&forecast, now);
+        assert_eq!(1, events.len());
+        assert_eq!((time("2023-10-19 19:00"), time("2023-10-19 23:00")), (events[0].start, events[0].until));
+
+        assert!("chance_of_rain > 60 within 3h".parse::<Rule>().unwrap().check(&forecast, now).is_empty());
+
+        let events = "alert rain".parse::<Rule>().unwrap().check(&forecast, now);
+        assert_eq!(1, events.len());
+        assert!(events[0].env().any(|(name, value)| name == "WEER_ALERT_EVENT" && value == "Yellow Warning for Rain"));
+        assert!("alert flood".parse::<Rule>().unwrap().check(&forecast, now).is_empty());
+    }
+
+    #[test]
+    fn alert_expiry_in_location_time() {
+        let mut forecast: Forecast = serde_json::from_str(fixtures::FORECAST).unwrap();
+        assert_eq!(FixedOffset::east_opt(3600), local_offset(&forecast));
+
+        // Same instant in Tokyo, the alert expires at 12:00 UTC
+        forecast.location.localtime = Some("2023-10-19 22:00".to_string());
+        assert_eq!(FixedOffset::east_opt(9 * 3600), local_offset(&forecast));
+
+        let now = local_now(&forecast);
+        let events = "alert rain".parse::<Rule>().unwrap().check(&forecast, now);
+        assert_eq!(time("2023-10-20 21:00"), events[0].until);
+
+        forecast.location.localtime = Some("2023-10-19 07:45".to_string());
+        assert_eq!(FixedOffset::west_opt(5 * 3600 + 15 * 60), local_offset(&forecast));
+    }
+}
@@ -1,60 +1,77 @@
-use std::error::Error;
-use weer_api::{Location, Current, ForecastDay, AirQuality};
-use prettytable::{Table, table, row};
-use super::image;
+use std::{borrow::Cow, error::Error};
+use weer_api::{Location, Current, ForecastDay, AirQuality, Language, Realtime, Forecast, Stats, Summary, DegreeDay, GrowingDegreeDay};
+use prettytable::{Table, Row, Cell, table, row};
+use super::{image, Config, config::Units, locale::{self, text}};
 
 
-pub fn current_table(current: Current) -> Result<Table, Box<dyn Error>> {
+fn icon(icon: &str, cfg: &Config) -> Result<String, Box<dyn Error>> {
+    if cfg.icons() {
+        image::convert(icon)
+    } else {
+        Ok(String::new())
+    }
+}
+
+pub fn current_table(current: Current, cfg: &Config) -> Result<Table, Box<dyn Error>> {
     let mut table = table!(
-        [image::convert(&current.condition.icon)?, current.condition.text],
-        ["Last updated", current.last_updated],
-        ["Temperature in celsius", current.temp_c],
-        ["Temperature in fahrenheit", current.temp_f],
-        // ["Condition", current.condition.text],
-        ["Is day", current.is_day()],
-        ["Humidity as percentage", format!("{}%", current.humidity)],
-        ["Cloud cover as percentage", format!("{}%", current.cloud)]
+        [icon(&current.condition.icon, cfg)?, current.condition.text],
+        [text("last-updated"), current.last_updated]
     );
 
+    match cfg.units() {
+        Units::Metric => table.add_row(row![text("temperature-celsius"), current.temp_c]),
+        Units::Imperial => table.add_row(row![text("temperature-fahrenheit"), current.temp_f])
+    };
+
+    table.extend([
+        // row!["Condition", current.condition.text],
+        row![text("is-day"), locale::yes_no(current.is_day())],
+        row![text("humidity"), format!("{}%", current.humidity)],
+        row![text("cloud-cover"), format!("{}%", current.cloud)]
+    ]);
+
     if let Some(air) = current.air_quality {
-        table.add_row(row!["Air quality", air_quality_table(air)]);
+        table.add_row(row![text("air-quality"), air_quality_table(air)]);
     }
 
     Ok(table)
 }
 
-pub fn forecastday_table(fd: &ForecastDay) -> Result<Table, Box<dyn Error>> {
-    let day_table = table!(
-        [image::convert(&fd.day.condition.icon)?, fd.day.condition.text],
-        ["Temperature in celsius", fd.day.temp_c()],
-        ["Temperature in fahrenheit", fd.day.temp_f()]
+pub fn forecastday_table(fd: &ForecastDay, cfg: &Config) -> Result<Table, Box<dyn Error>> {
+    let mut day_table = table!(
+        [icon(&fd.day.condition.icon, cfg)?, fd.day.condition.text]
     );
 
+    match cfg.units() {
+        Units::Metric => day_table.add_row(row![text("temperature-celsius"), fd.day.temp_c()]),
+        Units::Imperial => day_table.add_row(row![text("temperature-fahrenheit"), fd.day.temp_f()])
+    };
+
     let astro_table = table!(
-        ["Sunrise time", fd.astro.sunrise],
-        ["Sunset time", fd.astro.sunset],
-        ["Moonrise time", fd.astro.moonrise],
-        ["Moonset time", fd.astro.moonset],
-        ["Moon phase", fd.astro.moon_phase],
-        ["Moon illumination as %", format!("{}%", fd.astro.moon_illumination)]
+        [text("sunrise"), fd.astro.sunrise],
+        [text("sunset"), fd.astro.sunset],
+        [text("moonrise"), fd.astro.moonrise],
+        [text("moonset"), fd.astro.moonset],
+        [text("moon-phase"), locale::moon_phase(&fd.astro.moon_phase)],
+        [text("moon-illumination"), format!("{}%", fd.astro.moon_illumination)]
     );
     
-    Ok(table!(["Date", fd.date], ["Day", day_table], ["Astro", astro_table]))
+    Ok(table!([text("date"), fd.date], [text("day"), day_table], [text("astro"), astro_table]))
 }
 
 pub fn location_table(location: Location) -> Table {
     let mut table = table!(
-        ["Name", location.name], 
-        ["Country", location.country], 
-        ["Latitude and Longitude", format!("{}", location.coords)]
+        [text("name"), location.name], 
+        [text("country"), location.country], 
+        [text("coords"), format!("{}", location.coords)]
     );
 
     if let Some(tz_id) = location.tz_id {
-        table.add_row(row!["TimeZone ID", tz_id]);
+        table.add_row(row![text("tz-id"), tz_id]);
     }
 
     if let Some(localtime) = location.localtime {
-        table.add_row(row!["Localtime", localtime]);
+        table.add_row(row![text("localtime"), localtime]);
     }
 
     table
@@ -62,12 +79,266 @@ pub fn location_table(location: Location) -> Table {
 
 pub fn air_quality_table(air: AirQuality) -> Table {
     table!(
-        ["Carbon Monoxide (μg/m3)", air.carbon_monoxide],
-        ["Ozone (μg/m3)", air.ozone],
-        ["Nitrogen dioxide (μg/m3)", air.nitrogen_dioxide],
-        ["Sulphur dioxide (μg/m3)", air.sulphur_dioxide],
-        ["PM2.5 (μg/m3)", air.pm2_5],
-        ["PM10 (μg/m3)", air.pm10],
-        ["US - EPA standard", if air.us_epa_index <= 3 { "Low" } else if air.us_epa_index <= 6 { "Moderate" } else if air.us_epa_index <= 9 { "High" } else { "Very High" }]
+        [text("carbon-monoxide"), air.carbon_monoxide],
+        [text("ozone"), air.ozone],
+        [text("nitrogen-dioxide"), air.nitrogen_dioxide],
+        [text("sulphur-dioxide"), air.sulphur_dioxide],
+        [text("pm2-5"), air.pm2_5],
+        [text("pm10"), air.pm10],
+        [text("us-epa-index"), epa_level(air.us_epa_index)]
     )
 }
+
+pub fn epa_level(index: u32) -> Cow<'static, str> {
+    text(if index <= 3 { "aqi-low" } else if index <= 6 { "aqi-moderate" } else if index <= 9 { "aqi-high" } else { "aqi-very-high" })
+}
+
+/// Column of a comparison, the location as given by the user and its weather or why it couldn't be fetched
+pub type Column<T> = (String, Result<T, String>);
+
+/// Label of a comparison row and how its value is shown
+type Field<'a, T> = (&'static str, &'a dyn Fn(&T) -> String);
+
+pub fn compare_current_table(columns: &[Column<Realtime>], cfg: &Config) -> Table {
+    let mut table = compare_header(columns, |r| &r.location);
+
+    let rows: [Field<Realtime>; 5] = [
+        ("temperature", &|r| temperature(r.current.temp_c, r.current.temp_f, cfg.units())),
+        ("condition", &|r| r.current.condition.text.clone()),
+        ("precipitation", &|r| precipitation(r.current.precip_mm, r.current.precip_in, cfg.units())),
+        ("wind", &|r| wind(r.current.wind_kph, r.current.wind_mph, &r.current.wind_dir, cfg.units())),
+        ("aqi", &|r| aqi(r.current.air_quality.as_ref())),
+    ];
+
+    for (key, value) in rows {
+        table.add_row(compare_row(&text(key), columns, |r| Some(value(r))));
+    }
+
+    table
+}
+
+pub fn compare_forecast_table(columns: &[Column<Forecast>], cfg: &Config) -> Table {
+    let mut table = compare_header(columns, |f| &f.location);
+    let days = columns.iter()
+        .filter_map(|(_, f)| f.as_ref().ok())
+        .map(|f| f.forecast.forecast_day.len())
+        .max()
+        .unwrap_or(0);
+
+    let rows: [Field<ForecastDay>; 6] = [
+        ("date", &|fd| fd.date.clone()),
+        ("temperature", &|fd| format!(
+            "{} / {}",
+            temperature(fd.day.temp_c().min(), fd.day.temp_f().min(), cfg.units()),
+            temperature(fd.day.temp_c().max(), fd.day.temp_f().max(), cfg.units())
+        )),
+        ("condition", &|fd| fd.day.condition.text.clone()),
+        ("rain-chance", &|fd| fd.day.daily_chance_of_rain.map(|c| format!("{}%", c)).unwrap_or_default()),
+        ("wind", &|fd| wind(fd.day.maxwind_kph, fd.day.maxwind_mph, "", cfg.units())),
+        ("aqi", &|fd| aqi(fd.day.air_quality.as_ref())),
+    ];
+
+    for day in 0..days {
+        for (key, value) in rows.iter() {
+            table.add_row(compare_row(&text(key), columns, |f| f.forecast.forecast_day.get(day).map(value)));
+        }
+    }
+
+    table
+}
+
+fn compare_header<T>(columns: &[Column<T>], location: impl Fn(&T) -> &Location) -> Table {
+    let mut table = Table::new();
+    let mut header = vec![Cell::new("")];
+    let mut errors = vec![Cell::new(&text("error"))];
+
+    for (name, result) in columns {
+        match result {
+            Ok(t) => {
+                let location = location(t);
+                header.push(Cell::new(&format!("{}, {}", location.name, location.country)));
+                errors.push(Cell::new(""));
+            }
+            Err(err) => {
+                header.push(Cell::new(name));
+                errors.push(Cell::new(err));
+            }
+        }
+    }
+
+    table.set_titles(Row::new(header));
+    if columns.iter().any(|(_, result)| result.is_err()) {
+        table.add_row(Row::new(errors));
+    }
+
+    table
+}
+
+fn compare_row<T>(label: &str, columns: &[Column<T>], value: impl Fn(&T) -> Option<String>) -> Row {
+    let mut cells = vec![Cell::new(label)];
+    cells.extend(columns.iter().map(|(_, result)| {
+        Cell::new(&result.as_ref().ok().and_then(&value).unwrap_or_default())
+    }));
+
+    Row::new(cells)
+}
+
+/// A row per month and a last one for the whole span
+pub fn stats_table(stats: &Stats, units: Units) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new([
+        "stats-period", "stats-days", "stats-mean-temp", "stats-min-temp", "stats-max-temp", "precipitation",
+        "stats-rainy-days", "stats-mean-humidity", "stats-max-gust", "stats-daylight", "stats-sunny-hours", "uv-index"
+    ].iter().map(|key| Cell::new(&text(key))).collect()));
+
+    for month in &stats.months {
+        table.add_row(summary_row(month.start.format("%Y-%m").to_string(), month, units));
+    }
+    table.add_row(summary_row(format!("{} – {}", stats.period.start, stats.period.end), &stats.period, units));
+
+    table
+}
+
+fn summary_row(label: String, summary: &Summary, units: Units) -> Row {
+    let gust = summary.max_gust_kph.zip(summary.max_gust_mph).map(|(kph, mph)| wind(kph, mph, "", units));
+
+    Row::new([
+        label,
+        summary.days.to_string(),
+        temperature(summary.mean_temp_c, summary.mean_temp_f, units),
+        temperature(summary.min_temp_c, summary.min_temp_f, units),
+        temperature(summary.max_temp_c, summary.max_temp_f, units),
+        precipitation(summary.total_precip_mm, summary.total_precip_in, units),
+        summary.rainy_days.to_string(),
+        format!("{}%", summary.mean_humidity),
+        gust.unwrap_or_default(),
+        format!("{} h", summary.daylight_hours),
+        summary.sunny_hours.map(|hours| format!("{} h", hours)).unwrap_or_default(),
+        summary.mean_uv.to_string()
+    ].iter().map(|value| Cell::new(value)).collect())
+}
+
+pub fn degree_days_table(days: &[DegreeDay], units: Units) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new([
+        "date", "stats-mean-temp", "degree-days-hdd", "degree-days-cdd", "degree-days-cumulative-hdd", "degree-days-cumulative-cdd"
+    ].iter().map(|key| Cell::new(&text(key))).collect()));
+
+    for (day, values) in days.iter().zip(degree_day_values(days, units)) {
+        let [temp, hdd, cdd, cumulative_hdd, cumulative_cdd] = values;
+        let temp = match units {
+            Units::Metric => format!("{}°C", temp),
+            Units::Imperial => format!("{}°F", temp)
+        };
+        table.add_row(row![day.date, temp, hdd, cdd, cumulative_hdd, cumulative_cdd]);
+    }
+
+    table
+}
+
+/// Mean temperature, HDD, CDD and their running totals of each day
+///
+/// Degree days in fahrenheit are 1.8 times as many, their running totals add up the rounded values like in celsius.
+pub fn degree_day_values(days: &[DegreeDay], units: Units) -> Vec<[f32; 5]> {
+    let (mut cumulative_hdd, mut cumulative_cdd) = (0.0, 0.0);
+
+    days.iter()
+        .map(|day| match units {
+            Units::Metric => [day.temp_c, day.hdd, day.cdd, day.cumulative_hdd, day.cumulative_cdd],
+            Units::Imperial => {
+                let (hdd, cdd) = (round(day.hdd * 1.8), round(day.cdd * 1.8));
+                cumulative_hdd = round(cumulative_hdd + hdd);
+                cumulative_cdd = round(cumulative_cdd + cdd);
+                [round(day.temp_c * 1.8 + 32.0), hdd, cdd, cumulative_hdd, cumulative_cdd]
+            }
+        })
+        .collect()
+}
+
+/// A row per day, `gdd` and `et0` are those of `days`
+///
+/// The water balance is the precipitation minus the reference evapotranspiration since the first day.
+pub fn agro_table(days: &[ForecastDay], gdd: &[GrowingDegreeDay], et0: &[Option<f32>], units: Units) -> Table {
+    let mut table = Table::new();
+    table.set_titles(Row::new([
+        "date", "agro-gdd", "agro-cumulative-gdd", "agro-et0", "agro-cumulative-et0", "precipitation", "agro-water-balance"
+    ].iter().map(|key| Cell::new(&text(key))).collect()));
+
+    // Precipitations in millimetres, converted once added up
+    let depth = |mm: f32| match units {
+        Units::Metric => format!("{:.1} mm", mm),
+        Units::Imperial => format!("{:.2} in", mm / 25.4)
+    };
+    let (mut cumulative_gdd, mut cumulative_et0, mut balance) = (0.0, 0.0, 0.0);
+
+    for ((fd, gdd), et0) in days.iter().zip(gdd).zip(et0) {
+        let day_gdd = match units {
+            Units::Metric => gdd.gdd,
+            Units::Imperial => round(gdd.gdd * 1.8)
+        };
+        cumulative_gdd = round(cumulative_gdd + day_gdd);
+        cumulative_et0 += et0.unwrap_or(0.0);
+        balance += fd.day.totalprecip_mm - et0.unwrap_or(0.0);
+
+        table.add_row(row![
+            fd.date,
+            day_gdd,
+            cumulative_gdd,
+            et0.map(depth).unwrap_or_default(),
+            depth(cumulative_et0),
+            precipitation(fd.day.totalprecip_mm, fd.day.totalprecip_in, units),
+            depth(balance)
+        ]);
+    }
+
+    table
+}
+
+/// Rounded to a tenth, like the values of `weer_api`
+fn round(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
+
+pub fn temperature(c: f32, f: f32, units: Units) -> String {
+    match units {
+        Units::Metric => format!("{}°C", c),
+        Units::Imperial => format!("{}°F", f)
+    }
+}
+
+pub fn precipitation(mm: f32, inches: f32, units: Units) -> String {
+    match units {
+        Units::Metric => format!("{} mm", mm),
+        Units::Imperial => format!("{} in", inches)
+    }
+}
+
+pub fn wind(kph: f32, mph: f32, dir: &str, units: Units) -> String {
+    let speed = match units {
+        Units::Metric => format!("{} km/h", kph),
+        Units::Imperial => format!("{} mph", mph)
+    };
+
+    format!("{} {}", speed, dir).trim_end().to_string()
+}
+
+fn aqi(air: Option<&AirQuality>) -> String {
+    air.map(|air| format!("{} ({})", air.us_epa_index, epa_level(air.us_epa_index)))
+        .unwrap_or_default()
+}
+
+pub fn languages_table(selected: Option<Language>) -> Table {
+    let mut table = table!([text("code"), text("name"), text("native-name")]);
+
+    for lang in Language::all() {
+        let code = if Some(lang) == selected {
+            format!("{} *", lang.content())
+        } else {
+            lang.content().to_string()
+        };
+
+        table.add_row(row![code, lang.name(), lang.native_name()]);
+    }
+
+    table
+}
@@ -0,0 +1,142 @@
+//! Terminal side of `weer watch`
+//!
+//! The output of a command is redrawn on the alternate screen, with a countdown to the next refresh on the last line.
+
+use std::{
+    io::{self, Write},
+    error::Error,
+    time::{Duration, Instant}
+};
+
+use crossterm::{
+    cursor, execute, queue, style,
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    terminal::{self, ClearType}
+};
+use weer_api::chrono::{DateTime, Local};
+use colored::*;
+use super::{handler, locale::text};
+
+
+/// Output of the last successful refresh
+struct Snapshot {
+    output: String,
+    at: DateTime<Local>
+}
+
+/// Alternate screen in raw mode, left when dropped so that errors and panics restore the terminal
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Calls `render` every `interval` until the user quits
+///
+/// Transient errors keep the last good output on screen, marked as stale; any other error stops watching.
+pub fn run(
+    interval: Duration,
+    mut render: impl FnMut(&mut Vec<u8>) -> Result<(), Box<dyn Error>>
+) -> Result<(), Box<dyn Error>> {
+    let _screen = Screen::enter()?;
+    let mut out = io::stdout();
+    let mut last: Option<Snapshot> = None;
+
+    loop {
+        let mut buf = Vec::new();
+        let error = match render(&mut buf) {
+            Ok(()) => {
+                last = Some(Snapshot { output: String::from_utf8_lossy(&buf).into_owned(), at: Local::now() });
+                None
+            }
+            Err(err) => Some(transient(err)?)
+        };
+
+        let next = Instant::now() + interval;
+        draw(&mut out, last.as_ref(), error.as_deref())?;
+
+        while let Some(left) = next.checked_duration_since(Instant::now()) {
+            draw_status(&mut out, left)?;
+
+            if !event::poll(left.min(Duration::from_secs(1)))? {
+                continue;
+            }
+
+            match event::read()? {
+                Event::Key(key) if quits(&key) => return Ok(()),
+                Event::Key(KeyEvent { code: KeyCode::Char('r'), .. }) => break,
+                Event::Resize(_, _) => draw(&mut out, last.as_ref(), error.as_deref())?,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Message of an error worth retrying at the next refresh, the error itself otherwise
+fn transient(err: Box<dyn Error>) -> Result<String, Box<dyn Error>> {
+    let err = match err.downcast::<ureq::Error>() {
+        Ok(err) => *err,
+        Err(err) => return Err(err)
+    };
+
+    match err {
+        ureq::Error::Status(code, _) if code != 429 && code < 500 => Err(err.into()),
+        err => Ok(handler::describe_error(err))
+    }
+}
+
+fn quits(key: &KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => true,
+        KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+        _ => false
+    }
+}
+
+fn draw(out: &mut impl Write, last: Option<&Snapshot>, error: Option<&str>) -> io::Result<()> {
+    queue!(out, terminal::Clear(ClearType::All), cursor::MoveTo(0, 0))?;
+
+    match (last, error) {
+        (Some(last), Some(error)) => {
+            let stale = format!("{} {} ({})", text("stale"), last.at.format("%H:%M"), error);
+            write!(out, "{}\r\n", stale.yellow().bold())?;
+        }
+        (Some(last), None) => write!(out, "{} {}\r\n", text("last-updated").dimmed(), last.at.format("%H:%M").to_string().dimmed())?,
+        (None, Some(error)) => write!(out, "{}: {}\r\n", text("error").red().bold(), error)?,
+        (None, None) => {}
+    }
+
+    if let Some(last) = last {
+        // Raw mode doesn't return the carriage on line feeds
+        for line in last.output.lines() {
+            write!(out, "{}\r\n", line)?;
+        }
+    }
+
+    out.flush()
+}
+
+fn draw_status(out: &mut impl Write, left: Duration) -> io::Result<()> {
+    let (_, rows) = terminal::size()?;
+    let secs = left.as_secs();
+    let status = format!("{} {}:{:02} · {}", text("next-refresh"), secs / 60, secs % 60, text("quit-hint"));
+
+    queue!(
+        out,
+        cursor::MoveTo(0, rows.saturating_sub(1)),
+        terminal::Clear(ClearType::CurrentLine),
+        style::Print(status.dimmed())
+    )?;
+    out.flush()
+}
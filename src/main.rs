@@ -1,9 +1,20 @@
+// Requests fail with `ureq::Error`, like in weer_api
+#![allow(clippy::result_large_err)]
+
 mod app;
 use app::App;
+use std::{panic, process};
 
 fn main() {
-    let app = App::new();
-    if let Err(err) = app.run() {
-        eprintln!("{}", err)
-    }
+    app::catch_panics();
+
+    let err = match panic::catch_unwind(|| App::new().and_then(|app| app.run())) {
+        Ok(Ok(())) => return,
+        Ok(Err(err)) => err,
+        Err(payload) => app::panicked(payload)
+    };
+
+    let (message, code) = app::report(err);
+    eprintln!("{}", message);
+    process::exit(code)
 }
@@ -0,0 +1,211 @@
+use std::f64::consts::PI;
+use chrono::{DateTime, Datelike, NaiveDate, TimeZone, Timelike, Utc};
+use serde::Serialize;
+
+use crate::{Coords, Date, Forecast, ForecastDay, History, Hour, stats::round};
+
+
+/// Fewest hours a day needs for its reference evapotranspiration, those of a 3 hourly future
+pub const MIN_ET0_HOURS: usize = 8;
+
+/// Solar constant, MJ/m²/min
+const SOLAR_CONSTANT: f64 = 0.0820;
+
+/// Stefan-Boltzmann constant, MJ/K⁴/m²/h
+const STEFAN_BOLTZMANN: f64 = 2.043e-10;
+
+/// Albedo of the grass reference crop
+const ALBEDO: f64 = 0.23;
+
+/// Growing degree days, with the base and cap temperatures of a crop
+///
+/// Daily minimum and maximum temperatures are clamped between the base and the cap before their mean is compared to
+/// the base. Defaults to the 10°C base and 30°C cap of corn. Temperatures are in celsius.
+///
+/// # Example:
+/// ```no_run
+/// use weer_api::{*, chrono::{Utc, TimeZone}};
+///
+/// let client = Client::new("api_key", true);
+/// let history = client.history_range(
+///     Utc.with_ymd_and_hms(2023, 4, 1, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2023, 9, 30, 0, 0, 0).unwrap()
+/// )
+///     .query(Query::City("London".to_string()))
+///     .call()
+///     .unwrap();
+///
+/// // Tomatoes
+/// let mut gdd = GrowingDegreeDays::new();
+/// gdd.base(10.0)
+///     .cap(Some(32.0));
+///
+/// let days = history.growing_degree_days(&gdd);
+/// println!("GDD of the season: {}", days.last().unwrap().cumulative_gdd);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GrowingDegreeDays {
+    base: f32,
+    cap: Option<f32>
+}
+
+impl Default for GrowingDegreeDays {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Growing degree days of a day and of the days before it
+///
+/// Values are rounded to a tenth, the cumulative ones add up the rounded values.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GrowingDegreeDay {
+    pub date: NaiveDate,
+    pub gdd: f32,
+    pub cumulative_gdd: f32
+}
+
+impl GrowingDegreeDays {
+    /// 10°C base and 30°C cap
+    pub fn new() -> Self {
+        Self {
+            base: 10.0,
+            cap: Some(30.0)
+        }
+    }
+
+    /// Set up the base temperature, in celsius, below which the crop doesn't grow
+    pub fn base(&mut self, celsius: f32) -> &mut Self {
+        self.base = celsius;
+        self
+    }
+
+    /// Set up the cap temperature, in celsius, above which the crop grows no faster
+    pub fn cap(&mut self, celsius: Option<f32>) -> &mut Self {
+        self.cap = celsius;
+        self
+    }
+
+    /// Growing degree days of a single day
+    pub fn day(&self, fd: &ForecastDay) -> f32 {
+        let clamp = |t: f32| t.max(self.base).min(self.cap.unwrap_or(f32::INFINITY));
+        let mean = (clamp(fd.day.temp_c().min()) + clamp(fd.day.temp_c().max())) / 2.0;
+
+        round((mean - self.base).max(0.0))
+    }
+
+    /// Growing degree days of every day with their running total, days are expected in date order
+    pub fn days(&self, days: &[ForecastDay]) -> Vec<GrowingDegreeDay> {
+        let mut cumulative_gdd = 0.0;
+
+        days.iter()
+            .map(|fd| {
+                let gdd = self.day(fd);
+                cumulative_gdd = round(cumulative_gdd + gdd);
+
+                GrowingDegreeDay { date: fd.date(), gdd, cumulative_gdd }
+            })
+            .collect()
+    }
+}
+
+impl History {
+    /// Growing degree days of every day of the history, see [`GrowingDegreeDays`]
+    pub fn growing_degree_days(&self, gdd: &GrowingDegreeDays) -> Vec<GrowingDegreeDay> {
+        gdd.days(&self.forecast.forecast_day)
+    }
+}
+
+impl Forecast {
+    /// Growing degree days of every day of the forecast, see [`GrowingDegreeDays`]
+    pub fn growing_degree_days(&self, gdd: &GrowingDegreeDays) -> Vec<GrowingDegreeDay> {
+        gdd.days(&self.forecast.forecast_day)
+    }
+}
+
+impl Hour {
+    /// Reference evapotranspiration (ET0) over the hour, in millimetres, at the location of `coords`
+    ///
+    /// Follows the hourly Penman–Monteith equation of the FAO Irrigation and Drainage Paper 56, over the hour centred
+    /// on the time of `self`. The API has no solar radiation, it is estimated from the cloud cover with the Angström
+    /// formula, the cloud-free share of the sky standing for the relative sunshine duration. The wind speed is taken as
+    /// measured at 10 metres. Nights can give slightly negative values, condensation.
+    pub fn reference_evapotranspiration(&self, coords: &Coords) -> f32 {
+        let Some(time) = Utc.timestamp_opt(self.time_epoch, 0).single() else { return 0.0 };
+        let ra = extraterrestrial_radiation(time, coords);
+
+        // Angström formula with the default coefficients, the clear sky radiation is the one without clouds
+        let clear = 1.0 - self.cloud.min(100) as f64 / 100.0;
+        let rs = (0.25 + 0.50 * clear) * ra;
+        let rs_rso = (0.25 + 0.50 * clear) / 0.75;
+
+        // Wind speed at 2 metres, FAO-56 equation 47
+        let u2 = self.wind_kph as f64 / 3.6 * 4.87 / (67.8_f64 * 10.0 - 5.42).ln();
+
+        penman_monteith(self.temp_c as f64, self.humidity as f64, u2, self.pressure_mb as f64 / 10.0, rs, rs_rso) as f32
+    }
+}
+
+impl ForecastDay {
+    /// Reference evapotranspiration over the day, in millimetres, at the location of `coords`
+    ///
+    /// Hours stand for an equal share of the day. `None` with fewer than [`MIN_ET0_HOURS`] hours, e.g: when the day
+    /// was requested for a single hour.
+    pub fn reference_evapotranspiration(&self, coords: &Coords) -> Option<f32> {
+        if self.hour.len() < MIN_ET0_HOURS {
+            return None;
+        }
+
+        let hourly: f32 = self.hour.iter().map(|h| h.reference_evapotranspiration(coords)).sum();
+        Some(round((hourly * 24.0 / self.hour.len() as f32).max(0.0)))
+    }
+}
+
+/// Extraterrestrial radiation over the hour centred on `time`, in MJ/m², FAO-56 equation 28
+pub(crate) fn extraterrestrial_radiation(time: DateTime<Utc>, coords: &Coords) -> f64 {
+    let lat = (coords.lat as f64).to_radians();
+    let doy = time.ordinal() as f64;
+
+    let dr = 1.0 + 0.033 * (2.0 * PI * doy / 365.0).cos();
+    let declination = 0.409 * (2.0 * PI * doy / 365.0 - 1.39).sin();
+
+    // Solar time, the seasonal correction included
+    let b = 2.0 * PI * (doy - 81.0) / 364.0;
+    let correction = 0.1645 * (2.0 * b).sin() - 0.1255 * b.cos() - 0.025 * b.sin();
+    let utc = time.hour() as f64 + time.minute() as f64 / 60.0;
+    let solar = (utc + coords.lon as f64 / 15.0 + correction).rem_euclid(24.0);
+
+    let omega = PI / 12.0 * (solar - 12.0);
+    let sunset = (-lat.tan() * declination.tan()).clamp(-1.0, 1.0).acos();
+    let omega1 = (omega - PI / 24.0).clamp(-sunset, sunset);
+    let omega2 = (omega + PI / 24.0).clamp(-sunset, sunset);
+
+    let ra = 12.0 * 60.0 / PI * SOLAR_CONSTANT * dr * (
+        (omega2 - omega1) * lat.sin() * declination.sin()
+        + lat.cos() * declination.cos() * (omega2.sin() - omega1.sin())
+    );
+    ra.max(0.0)
+}
+
+/// Hourly reference evapotranspiration, in millimetres, FAO-56 equation 53
+///
+/// `t` in celsius, `rh` as percentage, `u2` in m/s, `pressure` in kPa, `rs` in MJ/m² and `rs_rso` the share of the
+/// clear sky radiation reaching the ground.
+pub(crate) fn penman_monteith(t: f64, rh: f64, u2: f64, pressure: f64, rs: f64, rs_rso: f64) -> f64 {
+    let es = 0.6108 * (17.27 * t / (t + 237.3)).exp();
+    let ea = es * rh / 100.0;
+    let delta = 4098.0 * es / (t + 237.3).powi(2);
+    let gamma = 0.000665 * pressure;
+
+    let rns = (1.0 - ALBEDO) * rs;
+    let rnl = STEFAN_BOLTZMANN * (t + 273.16).powi(4)
+        * (0.34 - 0.14 * ea.sqrt())
+        * (1.35 * rs_rso.clamp(0.3, 1.0) - 0.35);
+    let rn = rns - rnl;
+
+    // Soil heat flux, a larger share of the net radiation at night
+    let g = if rs > 0.0 { 0.1 * rn } else { 0.5 * rn };
+
+    (0.408 * delta * (rn - g) + gamma * 37.0 / (t + 273.0) * u2 * (es - ea))
+        / (delta + gamma * (1.0 + 0.34 * u2))
+}
@@ -1,9 +1,10 @@
 use url::Url;
 use ureq::Error;
 use serde::{Serialize, Deserialize};
-use std::{time::Duration, thread};
+use std::{thread, io, time::Instant};
+use tracing::{debug, info, info_span, warn};
 
-use crate::Client;
+use crate::{Client, Cassette, Retry, LimitAction, QuotaExceeded, redact};
 
 pub trait BaseApi<'a> 
 where
@@ -17,28 +18,154 @@ where
 
     fn call(&self) -> Result<Self::Model, ureq::Error> {
         let client = self.client();
-        let url = Url::parse_with_params(
-            format!(
-                "http{}://api.weatherapi.com/v1/{}.json", 
-                if client.https { "s" } else { "" }, 
-                self.path()
-            ).as_str(), 
-            &self.params()
-        )?;
-        
-        for _ in 1..3 {
-            match client.agent.request_url("GET", &url).call() {
-                Err(Error::Status(503, r)) | Err(Error::Status(429, r)) => {
-                    let retry: Option<u64> = r.header("retry-after")
-                        .and_then(|h| h.parse().ok());
-                    let retry = retry.unwrap_or(5);
-                    thread::sleep(Duration::from_secs(retry));
+        let params = self.params();
+
+        let span = info_span!("request", endpoint = self.path(), params = %redact::query(&params));
+        let _entered = span.enter();
+
+        // A cassette must see every request
+        let cache = client.cache.as_ref().filter(|_| client.cassette.is_none());
+
+        if let Some(cache) = cache {
+            match cache.get(self.path(), &params) {
+                Some(body) => {
+                    info!(cache = "hit", "answered from the cache");
+                    return parse(&body);
+                }
+                None => debug!(cache = "miss")
+            }
+        }
+
+        let body = match &client.cassette {
+            Some(cassette) if cassette.is_replay() => {
+                info!(cassette = %cassette.path().display(), "replayed");
+                replay(cassette, self.path(), &params)?
+            }
+            cassette => {
+                if let Some(quota) = &client.quota {
+                    let usage = quota.usage(&client.api_key)?;
+                    debug!(calls = usage.calls, budget = ?usage.budget, "quota");
+                    if usage.is_limited() {
+                        let stale = cache.filter(|_| quota.limit_action() == LimitAction::CacheOnly)
+                            .and_then(|c| c.get_stale(self.path(), &params));
+                        return match stale {
+                            Some(body) => {
+                                info!(cache = "stale", "answered from the cache, the quota is nearly used");
+                                parse(&body)
+                            }
+                            None => {
+                                warn!(calls = usage.calls, "refused, the quota is nearly used");
+                                Err(io::Error::other(QuotaExceeded { usage }).into())
+                            }
+                        };
+                    }
+                }
+
+                let base_url = match &client.base_url {
+                    Some(url) => url.clone(),
+                    None => format!("http{}://api.weatherapi.com/v1", if client.https { "s" } else { "" })
+                };
+                let url = Url::parse_with_params(format!("{}/{}.json", base_url, self.path()).as_str(), &params)?;
+
+                let result = request(client, &url);
+                match cassette {
+                    Some(cassette) => record(cassette, self.path(), &params, result)?,
+                    None => result?
                 }
-                result => return Ok(result?.into_json()?),
-            };
+            }
+        };
+        let model = parse(&body)?;
+
+        if let Some(cache) = cache {
+            // A cache that can't be written to is not worth failing the request
+            let _ = cache.put(self.path(), &params, &body);
+        }
+
+        Ok(model)
+    }
+}
+
+fn request(client: &Client, url: &Url) -> Result<String, ureq::Error> {
+    let start = Instant::now();
+    let mut attempt = 1;
+
+    loop {
+        if let Some(limiter) = &client.rate_limiter {
+            let waiting = Instant::now();
+            limiter.acquire();
+            debug!(waited_ms = waiting.elapsed().as_millis() as u64, "rate limiter");
         }
 
-        // Ran out of retries; try one last time and return whatever result we get.
-        Ok(client.agent.request_url("GET", &url).call()?.into_json()?)
+        let sent = Instant::now();
+        let result = client.agent.request_url("GET", url).call();
+        if let (Some(quota), Ok(_) | Err(Error::Status(..))) = (&client.quota, &result) {
+            // A counter that can't be written to is not worth failing the request
+            let _ = quota.count(&client.api_key);
+        }
+
+        let latency_ms = sent.elapsed().as_millis() as u64;
+        let error = match result {
+            Ok(resp) => {
+                info!(status = resp.status(), latency_ms = start.elapsed().as_millis() as u64, retries = attempt - 1, "response");
+                return Ok(resp.into_string()?);
+            }
+            Err(error) => error
+        };
+        // Redacting may change the kind of the error, the policy sees the original one
+        let delay = client.retry.delay(attempt, &error);
+        let error = redact::error(error, &client.api_key);
+        debug!(attempt, latency_ms, error = %error, "attempt failed");
+
+        let Some(delay) = delay else {
+            let total_ms = start.elapsed().as_millis() as u64;
+            match &error {
+                Error::Status(status, _) => info!(status, latency_ms = total_ms, retries = attempt - 1, "response"),
+                Error::Transport(_) => info!(latency_ms = total_ms, retries = attempt - 1, error = %error, "no response")
+            }
+            return Err(error);
+        };
+        warn!(attempt, delay_ms = delay.as_millis() as u64, error = %error, "retrying");
+        client.retry.notify(&Retry { attempt, delay, error: &error });
+        thread::sleep(delay);
+        attempt += 1;
+    }
+}
+
+fn replay(cassette: &Cassette, path: &str, params: &[(&str, String)]) -> Result<String, ureq::Error> {
+    match cassette.play(path, params)? {
+        (200..=299, body) => Ok(body),
+        (status, body) => Err(status_error(status, &body))
+    }
+}
+
+/// Records the response, failed ones included, and passes it on
+fn record(
+    cassette: &Cassette,
+    path: &str,
+    params: &[(&str, String)],
+    result: Result<String, ureq::Error>
+) -> Result<String, ureq::Error> {
+    match result {
+        Ok(body) => {
+            cassette.push(path, params, 200, &body)?;
+            Ok(body)
+        }
+        Err(Error::Status(status, resp)) => {
+            let body = resp.into_string()?;
+            cassette.push(path, params, status, &body)?;
+            Err(status_error(status, &body))
+        }
+        Err(err) => Err(err)
+    }
+}
+
+fn status_error(status: u16, body: &str) -> ureq::Error {
+    match ureq::Response::new(status, "", body) {
+        Ok(resp) => Error::Status(status, resp),
+        Err(err) => err
     }
 }
+
+fn parse<T: for<'de> Deserialize<'de>>(body: &str) -> Result<T, ureq::Error> {
+    Ok(serde_json::from_str(body).map_err(io::Error::from)?)
+}
@@ -0,0 +1,189 @@
+use std::{
+    fmt::Display,
+    io,
+    sync::{Mutex, atomic::{AtomicBool, AtomicUsize, Ordering}},
+    thread
+};
+use chrono::{DateTime, Duration, TimeZone};
+use tracing::info;
+
+use super::BaseApi;
+use crate::{Client, Query, Language, History};
+
+
+/// Most days a single history request can cover, `end_dt` is at most 30 days after `dt`
+pub const MAX_DAYS_PER_CALL: u32 = 30;
+
+/// `dt` and `end_dt` of a history request
+type Chunk<Tz> = (DateTime<Tz>, Option<DateTime<Tz>>);
+
+/// History of any number of days, fetched with as many history requests as needed
+///
+/// The requests go through the [`Client`] like any other, its cache, rate limiter, quota and retry policy included.
+pub struct HistoryRangeApi<'a, Tz: TimeZone>
+where
+    Tz::Offset: Display
+{
+    client: &'a Client,
+    query: Option<Query>,
+    start: DateTime<Tz>,
+    end: DateTime<Tz>,
+    hour: bool,
+    lang: Option<Language>,
+    days_per_call: u32,
+    concurrency: usize
+}
+
+impl<'a, Tz: TimeZone> HistoryRangeApi<'a, Tz>
+where
+    Tz::Offset: Display
+{
+    /// Use [`crate::Client`]
+    pub fn new(client: &'a Client, start: DateTime<Tz>, end: DateTime<Tz>) -> Self {
+        Self {
+            client,
+            query: None,
+            start,
+            end,
+            hour: false,
+            lang: None,
+            days_per_call: 1,
+            concurrency: 4
+        }
+    }
+
+    /// Set up the query
+    ///
+    /// Query parameter based on which data is sent back
+    pub fn query(&mut self, query: Query) -> &mut Self {
+        self.query = Some(query);
+        self
+    }
+
+    /// Set up use hour
+    ///
+    /// Time is extracted from the start datetime
+    pub fn hour(&mut self, hour: bool) -> &mut Self {
+        self.hour = hour;
+        self
+    }
+
+    /// Set up language
+    ///
+    /// `condition:text` field in API in the desired language
+    pub fn lang(&mut self, lang: Language) -> &mut Self {
+        self.lang = Some(lang);
+        self
+    }
+
+    /// Set up the days covered by each request, up to [`MAX_DAYS_PER_CALL`]
+    ///
+    /// Defaults to 1, free plans don't return more.
+    pub fn days_per_call(&mut self, days: u32) -> &mut Self {
+        self.days_per_call = days.clamp(1, MAX_DAYS_PER_CALL);
+        self
+    }
+
+    /// Set up the most requests sent at the same time, 4 by default
+    pub fn concurrency(&mut self, requests: usize) -> &mut Self {
+        self.concurrency = requests.max(1);
+        self
+    }
+
+    /// `dt` and `end_dt` of each request, in date order
+    fn chunks(&self) -> Result<Vec<Chunk<Tz>>, ureq::Error> {
+        let days = (self.end.date_naive() - self.start.date_naive()).num_days();
+        if days < 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "the end of the range is before its start").into());
+        }
+
+        let per_call = self.days_per_call as i64;
+        Ok((0..=days).step_by(per_call as usize)
+            .map(|first| {
+                let last = (first + per_call - 1).min(days);
+                let dt = self.start.clone() + Duration::days(first);
+                let end_dt = (last > first).then(|| self.start.clone() + Duration::days(last));
+                (dt, end_dt)
+            })
+            .collect())
+    }
+}
+
+impl<'a, Tz> HistoryRangeApi<'a, Tz>
+where
+    Tz: TimeZone + Sync,
+    Tz::Offset: Display + Sync
+{
+    /// Sends the requests and merges their days, in date order
+    ///
+    /// Fails with the error of the earliest days that failed, the requests not sent yet are dropped.
+    pub fn call(&self) -> Result<History, ureq::Error> {
+        let chunks = self.chunks()?;
+        info!(
+            start = %self.start.format("%Y-%m-%d"),
+            end = %self.end.format("%Y-%m-%d"),
+            requests = chunks.len(),
+            "history range"
+        );
+
+        let next = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let results = Mutex::new(Vec::with_capacity(chunks.len()));
+
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(chunks.len()) {
+                scope.spawn(|| {
+                    while !failed.load(Ordering::SeqCst) {
+                        let n = next.fetch_add(1, Ordering::SeqCst);
+                        let Some((dt, end_dt)) = chunks.get(n) else { break };
+
+                        let result = self.request(dt, end_dt.as_ref());
+                        if result.is_err() {
+                            failed.store(true, Ordering::SeqCst);
+                        }
+                        if let Ok(mut results) = results.lock() {
+                            results.push((n, result));
+                        }
+                    }
+                });
+            }
+        });
+
+        let mut results = results.into_inner().map_err(|_| io::Error::other("history range lock poisoned"))?;
+        results.sort_by_key(|(n, _)| *n);
+
+        let mut history: Option<History> = None;
+        for (_, result) in results {
+            let mut part = result?;
+            match &mut history {
+                Some(history) => history.forecast.forecast_day.append(&mut part.forecast.forecast_day),
+                None => history = Some(part)
+            }
+        }
+
+        let mut history = history.ok_or_else(|| io::Error::other("no history returned"))?;
+        let days = &mut history.forecast.forecast_day;
+        days.sort_by(|a, b| a.date.cmp(&b.date));
+        days.dedup_by(|a, b| a.date == b.date);
+
+        Ok(history)
+    }
+
+    fn request(&self, dt: &DateTime<Tz>, end_dt: Option<&DateTime<Tz>>) -> Result<History, ureq::Error> {
+        let mut req = self.client.history();
+        req.dt(dt.clone())
+            .hour(self.hour);
+
+        if let Some(query) = &self.query {
+            req.query(query.clone());
+        }
+        if let Some(end_dt) = end_dt {
+            req.end_dt(end_dt.clone());
+        }
+        if let Some(lang) = self.lang {
+            req.lang(lang);
+        }
+
+        req.call()
+    }
+}
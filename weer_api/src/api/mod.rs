@@ -10,6 +10,9 @@ pub use future::FutureApi;
 mod history;
 pub use history::HistoryApi;
 
+mod history_range;
+pub use history_range::{HistoryRangeApi, MAX_DAYS_PER_CALL};
+
 mod realtime;
 pub use realtime::RealtimeApi;
 
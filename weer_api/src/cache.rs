@@ -0,0 +1,101 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime}
+};
+
+
+/// On-disk cache of API responses
+///
+/// Responses are stored per endpoint and are only cached for the endpoints that have a time to live set up.
+///
+/// # Example:
+/// ```no_run
+/// use std::time::Duration;
+/// use weer_api::{Client, Cache};
+///
+/// let mut cache = Cache::new("/tmp/weer");
+/// cache.ttl("forecast", Duration::from_secs(3600));
+///
+/// let mut client = Client::new("api_key", true);
+/// client.cache(cache);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+    ttls: HashMap<String, Duration>
+}
+
+impl Cache {
+    /// Creates a new cache stored in `dir`
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            ttls: HashMap::new()
+        }
+    }
+
+    /// Set up the time to live of an endpoint
+    ///
+    /// `path` is the endpoint name, e.g: `current`, `forecast` or `history`. A zero `ttl` disables caching.
+    pub fn ttl(&mut self, path: &str, ttl: Duration) -> &mut Self {
+        self.ttls.insert(path.to_string(), ttl);
+        self
+    }
+
+    /// Directory where responses are stored
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Removes every stored response
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(())
+        }
+    }
+
+    /// Stored response that is still fresh
+    pub(crate) fn get(&self, path: &str, params: &[(&str, String)]) -> Option<String> {
+        let ttl = self.ttls.get(path).filter(|ttl| !ttl.is_zero())?;
+        let file = self.file(path, params);
+
+        let age = fs::metadata(&file)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+
+        if age > *ttl {
+            return None;
+        }
+
+        fs::read_to_string(file).ok()
+    }
+
+    /// Stored response whatever its age
+    pub(crate) fn get_stale(&self, path: &str, params: &[(&str, String)]) -> Option<String> {
+        fs::read_to_string(self.file(path, params)).ok()
+    }
+
+    pub(crate) fn put(&self, path: &str, params: &[(&str, String)], body: &str) -> io::Result<()> {
+        if self.ttls.get(path).is_none_or(|ttl| ttl.is_zero()) {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.dir)?;
+        fs::write(self.file(path, params), body)
+    }
+
+    /// File of a request, the api key is left out so that it never reaches the disk
+    fn file(&self, path: &str, params: &[(&str, String)]) -> PathBuf {
+        // Names and values end with a NUL, which neither holds, so that two requests never run together
+        let bytes: Vec<u8> = params.iter()
+            .filter(|(name, _)| *name != "key")
+            .flat_map(|(name, value)| [name.as_bytes(), b"\0", value.as_bytes(), b"\0"].concat())
+            .collect();
+
+        self.dir.join(format!("{}-{:016x}.json", path, crate::hash::fnv1a(&bytes)))
+    }
+}
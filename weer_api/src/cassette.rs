@@ -0,0 +1,128 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex}
+};
+use serde::{Serialize, Deserialize};
+
+use crate::redact;
+
+
+/// Requests and responses of a [`crate::Client`], saved to a file
+///
+/// A recording cassette rewrites its file after every response, the api key is redacted from it. A replaying
+/// cassette answers the requests from its file without touching the network: each request gets the first response
+/// recorded for the same endpoint and parameters that wasn't replayed yet, or the last one once they all were.
+///
+/// # Example:
+/// ```no_run
+/// use weer_api::{Client, Cassette};
+///
+/// let mut client = Client::new("api_key", true);
+/// client.cassette(Cassette::record("london.json"));
+///
+/// let mut client = Client::new("", true);
+/// client.cassette(Cassette::replay("london.json").unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    replay: bool,
+    tape: Arc<Mutex<Tape>>
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Tape {
+    interactions: Vec<Interaction>,
+    #[serde(skip)]
+    played: Vec<bool>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    endpoint: String,
+    params: Vec<(String, String)>,
+    status: u16,
+    /// JSON responses are kept as JSON to keep the file readable, anything else as a string
+    body: serde_json::Value
+}
+
+impl Cassette {
+    /// Records to `path`, overwriting it on the first response
+    pub fn record<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            replay: false,
+            tape: Default::default()
+        }
+    }
+
+    /// Replays the responses recorded in `path`
+    pub fn replay<P: Into<PathBuf>>(path: P) -> io::Result<Self> {
+        let path = path.into();
+        let mut tape: Tape = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        tape.played = vec![false; tape.interactions.len()];
+
+        Ok(Self {
+            path,
+            replay: true,
+            tape: Arc::new(Mutex::new(tape))
+        })
+    }
+
+    /// File of the cassette
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_replay(&self) -> bool {
+        self.replay
+    }
+
+    /// Status and body recorded for a request
+    pub(crate) fn play(&self, endpoint: &str, params: &[(&str, String)]) -> io::Result<(u16, String)> {
+        let params = redact::params(params);
+        let mut tape = self.tape.lock().map_err(|_| io::Error::other("cassette lock poisoned"))?;
+
+        let matching: Vec<usize> = tape.interactions.iter()
+            .enumerate()
+            .filter(|(_, i)| i.endpoint == endpoint && i.params == params)
+            .map(|(n, _)| n)
+            .collect();
+
+        let n = matching.iter()
+            .find(|n| !tape.played[**n])
+            .or(matching.last())
+            .copied()
+            .ok_or_else(|| io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no response recorded in {} for {} {:?}", self.path.display(), endpoint, params)
+            ))?;
+
+        tape.played[n] = true;
+        let interaction = &tape.interactions[n];
+        let body = match &interaction.body {
+            serde_json::Value::String(s) => s.clone(),
+            body => body.to_string()
+        };
+
+        Ok((interaction.status, body))
+    }
+
+    /// Adds a response and rewrites the file
+    pub(crate) fn push(&self, endpoint: &str, params: &[(&str, String)], status: u16, body: &str) -> io::Result<()> {
+        let mut tape = self.tape.lock().map_err(|_| io::Error::other("cassette lock poisoned"))?;
+
+        tape.interactions.push(Interaction {
+            endpoint: endpoint.to_string(),
+            params: redact::params(params),
+            status,
+            body: serde_json::from_str(body).unwrap_or_else(|_| serde_json::Value::String(body.to_string()))
+        });
+
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&*tape)?)
+    }
+}
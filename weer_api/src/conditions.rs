@@ -0,0 +1,435 @@
+use std::{cmp::Ordering, sync::OnceLock};
+use serde::{Serialize, Deserialize, Serializer, Deserializer};
+
+use crate::Language;
+
+const CATALOGUE: &str = include_str!("../data/weather_conditions.json");
+
+static CONDITIONS: OnceLock<Vec<ConditionInfo>> = OnceLock::new();
+
+
+/// Weather condition code
+///
+/// Codes that are not part of the bundled catalogue deserialize into [`ConditionCode::Unknown`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ConditionCode {
+    Sunny,
+    PartlyCloudy,
+    Cloudy,
+    Overcast,
+    Mist,
+    PatchyRainPossible,
+    PatchySnowPossible,
+    PatchySleetPossible,
+    PatchyFreezingDrizzlePossible,
+    ThunderyOutbreaksPossible,
+    BlowingSnow,
+    Blizzard,
+    Fog,
+    FreezingFog,
+    PatchyLightDrizzle,
+    LightDrizzle,
+    FreezingDrizzle,
+    HeavyFreezingDrizzle,
+    PatchyLightRain,
+    LightRain,
+    ModerateRainAtTimes,
+    ModerateRain,
+    HeavyRainAtTimes,
+    HeavyRain,
+    LightFreezingRain,
+    ModerateOrHeavyFreezingRain,
+    LightSleet,
+    ModerateOrHeavySleet,
+    PatchyLightSnow,
+    LightSnow,
+    PatchyModerateSnow,
+    ModerateSnow,
+    PatchyHeavySnow,
+    HeavySnow,
+    IcePellets,
+    LightRainShower,
+    ModerateOrHeavyRainShower,
+    TorrentialRainShower,
+    LightSleetShowers,
+    ModerateOrHeavySleetShowers,
+    LightSnowShowers,
+    ModerateOrHeavySnowShowers,
+    LightShowersOfIcePellets,
+    ModerateOrHeavyShowersOfIcePellets,
+    PatchyLightRainWithThunder,
+    ModerateOrHeavyRainWithThunder,
+    PatchyLightSnowWithThunder,
+    ModerateOrHeavySnowWithThunder,
+    Unknown(u32)
+}
+
+impl ConditionCode {
+    /// Every known condition
+    pub const ALL: [ConditionCode; 48] = [
+        ConditionCode::Sunny,
+        ConditionCode::PartlyCloudy,
+        ConditionCode::Cloudy,
+        ConditionCode::Overcast,
+        ConditionCode::Mist,
+        ConditionCode::PatchyRainPossible,
+        ConditionCode::PatchySnowPossible,
+        ConditionCode::PatchySleetPossible,
+        ConditionCode::PatchyFreezingDrizzlePossible,
+        ConditionCode::ThunderyOutbreaksPossible,
+        ConditionCode::BlowingSnow,
+        ConditionCode::Blizzard,
+        ConditionCode::Fog,
+        ConditionCode::FreezingFog,
+        ConditionCode::PatchyLightDrizzle,
+        ConditionCode::LightDrizzle,
+        ConditionCode::FreezingDrizzle,
+        ConditionCode::HeavyFreezingDrizzle,
+        ConditionCode::PatchyLightRain,
+        ConditionCode::LightRain,
+        ConditionCode::ModerateRainAtTimes,
+        ConditionCode::ModerateRain,
+        ConditionCode::HeavyRainAtTimes,
+        ConditionCode::HeavyRain,
+        ConditionCode::LightFreezingRain,
+        ConditionCode::ModerateOrHeavyFreezingRain,
+        ConditionCode::LightSleet,
+        ConditionCode::ModerateOrHeavySleet,
+        ConditionCode::PatchyLightSnow,
+        ConditionCode::LightSnow,
+        ConditionCode::PatchyModerateSnow,
+        ConditionCode::ModerateSnow,
+        ConditionCode::PatchyHeavySnow,
+        ConditionCode::HeavySnow,
+        ConditionCode::IcePellets,
+        ConditionCode::LightRainShower,
+        ConditionCode::ModerateOrHeavyRainShower,
+        ConditionCode::TorrentialRainShower,
+        ConditionCode::LightSleetShowers,
+        ConditionCode::ModerateOrHeavySleetShowers,
+        ConditionCode::LightSnowShowers,
+        ConditionCode::ModerateOrHeavySnowShowers,
+        ConditionCode::LightShowersOfIcePellets,
+        ConditionCode::ModerateOrHeavyShowersOfIcePellets,
+        ConditionCode::PatchyLightRainWithThunder,
+        ConditionCode::ModerateOrHeavyRainWithThunder,
+        ConditionCode::PatchyLightSnowWithThunder,
+        ConditionCode::ModerateOrHeavySnowWithThunder
+    ];
+
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1000 => ConditionCode::Sunny,
+            1003 => ConditionCode::PartlyCloudy,
+            1006 => ConditionCode::Cloudy,
+            1009 => ConditionCode::Overcast,
+            1030 => ConditionCode::Mist,
+            1063 => ConditionCode::PatchyRainPossible,
+            1066 => ConditionCode::PatchySnowPossible,
+            1069 => ConditionCode::PatchySleetPossible,
+            1072 => ConditionCode::PatchyFreezingDrizzlePossible,
+            1087 => ConditionCode::ThunderyOutbreaksPossible,
+            1114 => ConditionCode::BlowingSnow,
+            1117 => ConditionCode::Blizzard,
+            1135 => ConditionCode::Fog,
+            1147 => ConditionCode::FreezingFog,
+            1150 => ConditionCode::PatchyLightDrizzle,
+            1153 => ConditionCode::LightDrizzle,
+            1168 => ConditionCode::FreezingDrizzle,
+            1171 => ConditionCode::HeavyFreezingDrizzle,
+            1180 => ConditionCode::PatchyLightRain,
+            1183 => ConditionCode::LightRain,
+            1186 => ConditionCode::ModerateRainAtTimes,
+            1189 => ConditionCode::ModerateRain,
+            1192 => ConditionCode::HeavyRainAtTimes,
+            1195 => ConditionCode::HeavyRain,
+            1198 => ConditionCode::LightFreezingRain,
+            1201 => ConditionCode::ModerateOrHeavyFreezingRain,
+            1204 => ConditionCode::LightSleet,
+            1207 => ConditionCode::ModerateOrHeavySleet,
+            1210 => ConditionCode::PatchyLightSnow,
+            1213 => ConditionCode::LightSnow,
+            1216 => ConditionCode::PatchyModerateSnow,
+            1219 => ConditionCode::ModerateSnow,
+            1222 => ConditionCode::PatchyHeavySnow,
+            1225 => ConditionCode::HeavySnow,
+            1237 => ConditionCode::IcePellets,
+            1240 => ConditionCode::LightRainShower,
+            1243 => ConditionCode::ModerateOrHeavyRainShower,
+            1246 => ConditionCode::TorrentialRainShower,
+            1249 => ConditionCode::LightSleetShowers,
+            1252 => ConditionCode::ModerateOrHeavySleetShowers,
+            1255 => ConditionCode::LightSnowShowers,
+            1258 => ConditionCode::ModerateOrHeavySnowShowers,
+            1261 => ConditionCode::LightShowersOfIcePellets,
+            1264 => ConditionCode::ModerateOrHeavyShowersOfIcePellets,
+            1273 => ConditionCode::PatchyLightRainWithThunder,
+            1276 => ConditionCode::ModerateOrHeavyRainWithThunder,
+            1279 => ConditionCode::PatchyLightSnowWithThunder,
+            1282 => ConditionCode::ModerateOrHeavySnowWithThunder,
+            code => ConditionCode::Unknown(code)
+        }
+    }
+
+    pub fn code(&self) -> u32 {
+        match self {
+            ConditionCode::Sunny => 1000,
+            ConditionCode::PartlyCloudy => 1003,
+            ConditionCode::Cloudy => 1006,
+            ConditionCode::Overcast => 1009,
+            ConditionCode::Mist => 1030,
+            ConditionCode::PatchyRainPossible => 1063,
+            ConditionCode::PatchySnowPossible => 1066,
+            ConditionCode::PatchySleetPossible => 1069,
+            ConditionCode::PatchyFreezingDrizzlePossible => 1072,
+            ConditionCode::ThunderyOutbreaksPossible => 1087,
+            ConditionCode::BlowingSnow => 1114,
+            ConditionCode::Blizzard => 1117,
+            ConditionCode::Fog => 1135,
+            ConditionCode::FreezingFog => 1147,
+            ConditionCode::PatchyLightDrizzle => 1150,
+            ConditionCode::LightDrizzle => 1153,
+            ConditionCode::FreezingDrizzle => 1168,
+            ConditionCode::HeavyFreezingDrizzle => 1171,
+            ConditionCode::PatchyLightRain => 1180,
+            ConditionCode::LightRain => 1183,
+            ConditionCode::ModerateRainAtTimes => 1186,
+            ConditionCode::ModerateRain => 1189,
+            ConditionCode::HeavyRainAtTimes => 1192,
+            ConditionCode::HeavyRain => 1195,
+            ConditionCode::LightFreezingRain => 1198,
+            ConditionCode::ModerateOrHeavyFreezingRain => 1201,
+            ConditionCode::LightSleet => 1204,
+            ConditionCode::ModerateOrHeavySleet => 1207,
+            ConditionCode::PatchyLightSnow => 1210,
+            ConditionCode::LightSnow => 1213,
+            ConditionCode::PatchyModerateSnow => 1216,
+            ConditionCode::ModerateSnow => 1219,
+            ConditionCode::PatchyHeavySnow => 1222,
+            ConditionCode::HeavySnow => 1225,
+            ConditionCode::IcePellets => 1237,
+            ConditionCode::LightRainShower => 1240,
+            ConditionCode::ModerateOrHeavyRainShower => 1243,
+            ConditionCode::TorrentialRainShower => 1246,
+            ConditionCode::LightSleetShowers => 1249,
+            ConditionCode::ModerateOrHeavySleetShowers => 1252,
+            ConditionCode::LightSnowShowers => 1255,
+            ConditionCode::ModerateOrHeavySnowShowers => 1258,
+            ConditionCode::LightShowersOfIcePellets => 1261,
+            ConditionCode::ModerateOrHeavyShowersOfIcePellets => 1264,
+            ConditionCode::PatchyLightRainWithThunder => 1273,
+            ConditionCode::ModerateOrHeavyRainWithThunder => 1276,
+            ConditionCode::PatchyLightSnowWithThunder => 1279,
+            ConditionCode::ModerateOrHeavySnowWithThunder => 1282,
+            ConditionCode::Unknown(code) => *code
+        }
+    }
+
+    /// Entry of the bundled catalogue
+    pub fn info(&self) -> Option<&'static ConditionInfo> {
+        conditions().iter().find(|c| c.code == *self)
+    }
+
+    /// Rain, drizzle, sleet, snow or ice pellets, including the possible and patchy ones
+    pub fn is_precipitation(&self) -> bool {
+        !matches!(
+            self,
+            ConditionCode::Sunny
+                | ConditionCode::PartlyCloudy
+                | ConditionCode::Cloudy
+                | ConditionCode::Overcast
+                | ConditionCode::Mist
+                | ConditionCode::Fog
+                | ConditionCode::FreezingFog
+                | ConditionCode::ThunderyOutbreaksPossible
+                | ConditionCode::Unknown(_)
+        )
+    }
+
+    /// Snow, including sleet and blowing snow
+    pub fn is_snow(&self) -> bool {
+        matches!(
+            self,
+            ConditionCode::PatchySnowPossible
+                | ConditionCode::PatchySleetPossible
+                | ConditionCode::BlowingSnow
+                | ConditionCode::Blizzard
+                | ConditionCode::LightSleet
+                | ConditionCode::ModerateOrHeavySleet
+                | ConditionCode::PatchyLightSnow
+                | ConditionCode::LightSnow
+                | ConditionCode::PatchyModerateSnow
+                | ConditionCode::ModerateSnow
+                | ConditionCode::PatchyHeavySnow
+                | ConditionCode::HeavySnow
+                | ConditionCode::LightSleetShowers
+                | ConditionCode::ModerateOrHeavySleetShowers
+                | ConditionCode::LightSnowShowers
+                | ConditionCode::ModerateOrHeavySnowShowers
+                | ConditionCode::PatchyLightSnowWithThunder
+                | ConditionCode::ModerateOrHeavySnowWithThunder
+        )
+    }
+
+    pub fn is_thunder(&self) -> bool {
+        matches!(
+            self,
+            ConditionCode::ThunderyOutbreaksPossible
+                | ConditionCode::PatchyLightRainWithThunder
+                | ConditionCode::ModerateOrHeavyRainWithThunder
+                | ConditionCode::PatchyLightSnowWithThunder
+                | ConditionCode::ModerateOrHeavySnowWithThunder
+        )
+    }
+
+    /// Mist, fog or freezing fog
+    pub fn is_fog(&self) -> bool {
+        matches!(self, ConditionCode::Mist | ConditionCode::Fog | ConditionCode::FreezingFog)
+    }
+
+    /// How severe the condition is, unknown codes are [`Severity::Clear`]
+    pub fn severity(&self) -> Severity {
+        match self {
+            ConditionCode::Sunny | ConditionCode::Unknown(_) => Severity::Clear,
+            ConditionCode::PartlyCloudy
+                | ConditionCode::Cloudy
+                | ConditionCode::Overcast
+                | ConditionCode::Mist
+                | ConditionCode::Fog => Severity::Cloudy,
+            ConditionCode::PatchyRainPossible
+                | ConditionCode::PatchySnowPossible
+                | ConditionCode::PatchySleetPossible
+                | ConditionCode::PatchyFreezingDrizzlePossible
+                | ConditionCode::FreezingFog
+                | ConditionCode::PatchyLightDrizzle
+                | ConditionCode::LightDrizzle
+                | ConditionCode::PatchyLightRain
+                | ConditionCode::LightRain
+                | ConditionCode::LightSleet
+                | ConditionCode::PatchyLightSnow
+                | ConditionCode::LightSnow
+                | ConditionCode::LightRainShower
+                | ConditionCode::LightSleetShowers
+                | ConditionCode::LightSnowShowers => Severity::Light,
+            ConditionCode::ThunderyOutbreaksPossible
+                | ConditionCode::BlowingSnow
+                | ConditionCode::FreezingDrizzle
+                | ConditionCode::ModerateRainAtTimes
+                | ConditionCode::ModerateRain
+                | ConditionCode::LightFreezingRain
+                | ConditionCode::PatchyModerateSnow
+                | ConditionCode::ModerateSnow
+                | ConditionCode::IcePellets
+                | ConditionCode::LightShowersOfIcePellets => Severity::Moderate,
+            ConditionCode::HeavyFreezingDrizzle
+                | ConditionCode::HeavyRainAtTimes
+                | ConditionCode::HeavyRain
+                | ConditionCode::ModerateOrHeavyFreezingRain
+                | ConditionCode::ModerateOrHeavySleet
+                | ConditionCode::PatchyHeavySnow
+                | ConditionCode::HeavySnow
+                | ConditionCode::ModerateOrHeavyRainShower
+                | ConditionCode::ModerateOrHeavySleetShowers
+                | ConditionCode::ModerateOrHeavySnowShowers
+                | ConditionCode::ModerateOrHeavyShowersOfIcePellets
+                | ConditionCode::PatchyLightRainWithThunder
+                | ConditionCode::PatchyLightSnowWithThunder => Severity::Heavy,
+            ConditionCode::Blizzard
+                | ConditionCode::TorrentialRainShower
+                | ConditionCode::ModerateOrHeavyRainWithThunder
+                | ConditionCode::ModerateOrHeavySnowWithThunder => Severity::Severe
+        }
+    }
+}
+
+/// Conditions are ordered by severity, then by code
+impl Ord for ConditionCode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.severity().cmp(&other.severity())
+            .then_with(|| self.code().cmp(&other.code()))
+    }
+}
+
+impl PartialOrd for ConditionCode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Serialize for ConditionCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for ConditionCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u32::deserialize(deserializer).map(ConditionCode::from_code)
+    }
+}
+
+
+/// Severity of a [`ConditionCode`], from the mildest to the most severe
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Severity {
+    Clear,
+    Cloudy,
+    Light,
+    Moderate,
+    Heavy,
+    Severe
+}
+
+
+/// Day and night texts of a condition in a language
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ConditionText {
+    pub lang_name: String,
+    pub lang_iso: String,
+    pub day_text: String,
+    pub night_text: String
+}
+
+
+/// Entry of the Weather API condition catalogue
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct ConditionInfo {
+    pub code: ConditionCode,
+    pub day: String,
+    pub night: String,
+    pub icon: u32,
+    #[serde(default)]
+    pub languages: Vec<ConditionText>
+}
+
+impl ConditionInfo {
+    /// Text of the condition, in English when `lang` is not translated
+    pub fn text(&self, is_day: bool, lang: Option<Language>) -> &str {
+        let translation = lang.and_then(|lang| {
+            self.languages.iter().find(|t| t.lang_iso == lang.content())
+        });
+
+        match (translation, is_day) {
+            (Some(t), true) => &t.day_text,
+            (Some(t), false) => &t.night_text,
+            (None, true) => &self.day,
+            (None, false) => &self.night
+        }
+    }
+
+    /// Path of the 64x64 icon, in the same form as `Condition::icon`
+    pub fn icon(&self, is_day: bool) -> String {
+        format!(
+            "//cdn.weatherapi.com/weather/64x64/{}/{}.png",
+            if is_day { "day" } else { "night" },
+            self.icon
+        )
+    }
+}
+
+/// Condition catalogue bundled with the crate
+pub fn conditions() -> &'static [ConditionInfo] {
+    CONDITIONS.get_or_init(|| {
+        serde_json::from_str(CATALOGUE).expect("the bundled condition catalogue is valid")
+    })
+}
@@ -0,0 +1,154 @@
+use chrono::NaiveDate;
+use serde::{Serialize, Deserialize};
+
+use crate::{Date, Forecast, ForecastDay, History, stats::round};
+
+
+/// How the mean temperature of a day is taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DegreeDayMethod {
+    /// Mean of the minimum and the maximum temperature of the day
+    #[default]
+    MinMax,
+    /// Average temperature of the day as sent by the API
+    Average,
+    /// Hourly integration, each hour weighs a 24th of the day
+    ///
+    /// Days without hourly data, e.g: requested for a single hour, fall back to [`DegreeDayMethod::MinMax`].
+    Hourly
+}
+
+/// Heating and cooling degree days, with their base temperatures and method
+///
+/// A day below the heating base counts the difference as heating degree days (HDD), a day above the cooling base
+/// counts it as cooling degree days (CDD). Both bases default to 18°C. Temperatures are in celsius, degree days in
+/// fahrenheit are 1.8 times as many with bases converted to celsius.
+///
+/// # Example:
+/// ```no_run
+/// use weer_api::{*, chrono::{Utc, TimeZone}};
+///
+/// let client = Client::new("api_key", true);
+/// let history = client.history_range(
+///     Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2023, 1, 31, 0, 0, 0).unwrap()
+/// )
+///     .query(Query::City("London".to_string()))
+///     .call()
+///     .unwrap();
+///
+/// let mut degree_days = DegreeDays::new();
+/// degree_days.heating_base(15.5)
+///     .method(DegreeDayMethod::Hourly);
+///
+/// let days = history.degree_days(&degree_days);
+/// println!("HDD in January: {}", days.last().unwrap().cumulative_hdd);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DegreeDays {
+    heating_base: f32,
+    cooling_base: f32,
+    method: DegreeDayMethod
+}
+
+impl Default for DegreeDays {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Degree days of a day and of the days before it
+///
+/// Values are rounded to a tenth, the cumulative ones add up the rounded values.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DegreeDay {
+    pub date: NaiveDate,
+    /// Mean temperature of the day, taken with the method of the [`DegreeDays`]
+    pub temp_c: f32,
+    pub hdd: f32,
+    pub cdd: f32,
+    pub cumulative_hdd: f32,
+    pub cumulative_cdd: f32
+}
+
+impl DegreeDays {
+    /// 18°C bases and [`DegreeDayMethod::MinMax`]
+    pub fn new() -> Self {
+        Self {
+            heating_base: 18.0,
+            cooling_base: 18.0,
+            method: DegreeDayMethod::MinMax
+        }
+    }
+
+    /// Set up the base temperature of the heating degree days, in celsius
+    pub fn heating_base(&mut self, celsius: f32) -> &mut Self {
+        self.heating_base = celsius;
+        self
+    }
+
+    /// Set up the base temperature of the cooling degree days, in celsius
+    pub fn cooling_base(&mut self, celsius: f32) -> &mut Self {
+        self.cooling_base = celsius;
+        self
+    }
+
+    /// Set up how the mean temperature of a day is taken
+    pub fn method(&mut self, method: DegreeDayMethod) -> &mut Self {
+        self.method = method;
+        self
+    }
+
+    /// Heating and cooling degree days of a single day
+    pub fn day(&self, fd: &ForecastDay) -> (f32, f32) {
+        let (hdd, cdd, _) = self.compute(fd);
+        (hdd, cdd)
+    }
+
+    /// Degree days of every day with their running totals, days are expected in date order
+    pub fn days(&self, days: &[ForecastDay]) -> Vec<DegreeDay> {
+        let (mut cumulative_hdd, mut cumulative_cdd) = (0.0, 0.0);
+
+        days.iter()
+            .map(|fd| {
+                let (hdd, cdd, temp_c) = self.compute(fd);
+                cumulative_hdd = round(cumulative_hdd + hdd);
+                cumulative_cdd = round(cumulative_cdd + cdd);
+
+                DegreeDay { date: fd.date(), temp_c, hdd, cdd, cumulative_hdd, cumulative_cdd }
+            })
+            .collect()
+    }
+
+    /// HDD, CDD and the mean temperature of a day
+    fn compute(&self, fd: &ForecastDay) -> (f32, f32, f32) {
+        let temps: Vec<f32> = match self.method {
+            DegreeDayMethod::Hourly if !fd.hour.is_empty() => fd.hour.iter().map(|h| h.temp_c).collect(),
+            DegreeDayMethod::Average => vec![fd.day.avgtemp_c],
+            _ => vec![(fd.day.temp_c().min() + fd.day.temp_c().max()) / 2.0]
+        };
+
+        // Each temperature stands for an equal share of the day
+        let share = |degrees: &dyn Fn(f32) -> f32| round(temps.iter().map(|t| degrees(*t)).sum::<f32>() / temps.len() as f32);
+        let hdd = share(&|t| (self.heating_base - t).max(0.0));
+        let cdd = share(&|t| (t - self.cooling_base).max(0.0));
+        let temp_c = share(&|t| t);
+
+        (hdd, cdd, temp_c)
+    }
+}
+
+impl History {
+    /// Degree days of every day of the history, see [`DegreeDays`]
+    pub fn degree_days(&self, degree_days: &DegreeDays) -> Vec<DegreeDay> {
+        degree_days.days(&self.forecast.forecast_day)
+    }
+}
+
+impl Forecast {
+    /// Degree days of every day of the forecast, see [`DegreeDays`]
+    pub fn degree_days(&self, degree_days: &DegreeDays) -> Vec<DegreeDay> {
+        degree_days.days(&self.forecast.forecast_day)
+    }
+}
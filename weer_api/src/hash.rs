@@ -0,0 +1,7 @@
+/// 64 bits FNV-1a hash of `bytes`
+///
+/// Names of files written by a version are read back by the next ones, so the hash must not change between Rust
+/// releases as the one of `std` can.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
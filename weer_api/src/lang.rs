@@ -1,4 +1,6 @@
 use std::fmt::{self, Display};
+use std::str::FromStr;
+use std::error::Error;
 use serde::{Serialize, Deserialize};
 
 
@@ -87,6 +89,159 @@ pub enum Language {
 }
 
 impl Language {
+    const ALL: [Language; 40] = [
+        Language::Arabic,
+        Language::Bengali,
+        Language::Bulgarian,
+        Language::ChineseSimplified,
+        Language::ChineseTraditional,
+        Language::Czech,
+        Language::Danish,
+        Language::Dutch,
+        Language::Finnish,
+        Language::French,
+        Language::German,
+        Language::Greek,
+        Language::Hindi,
+        Language::Hungarian,
+        Language::Italian,
+        Language::Japanese,
+        Language::Javanese,
+        Language::Korean,
+        Language::Mandarin,
+        Language::Marathi,
+        Language::Polish,
+        Language::Portuguese,
+        Language::Punjabi,
+        Language::Romanian,
+        Language::Russian,
+        Language::Serbian,
+        Language::Sinhalese,
+        Language::Slovak,
+        Language::Spanish,
+        Language::Swedish,
+        Language::Tamil,
+        Language::Telugu,
+        Language::Turkish,
+        Language::Ukrainian,
+        Language::Urdu,
+        Language::Vietnamese,
+        Language::WuShanghainese,
+        Language::Xiang,
+        Language::YueCantonese,
+        Language::Zulu
+    ];
+
+    /// Every language supported by the API
+    pub fn all() -> impl Iterator<Item = Language> {
+        Self::ALL.into_iter()
+    }
+
+    /// Language of a POSIX locale such as `es_AR.UTF-8`
+    ///
+    /// Returns `None` for English, `C` and `POSIX`, the API answers in English by default.
+    pub fn from_locale(locale: &str) -> Option<Language> {
+        let tag = locale.split(['.', '@']).next()?.replace('-', "_").to_lowercase();
+        let (lang, _) = tag.split_once('_').unwrap_or((&tag, ""));
+
+        match tag.as_str() {
+            "zh_tw" | "zh_hk" | "zh_mo" => Some(Language::ChineseTraditional),
+            _ => Self::all().find(|l| l.content() == tag || l.content() == lang)
+        }
+    }
+
+    /// English name of the language
+    pub fn name(&self) -> &str {
+        match self {
+            Language::Arabic => "Arabic",
+            Language::Bengali => "Bengali",
+            Language::Bulgarian => "Bulgarian",
+            Language::ChineseSimplified => "Chinese Simplified",
+            Language::ChineseTraditional => "Chinese Traditional",
+            Language::Czech => "Czech",
+            Language::Danish => "Danish",
+            Language::Dutch => "Dutch",
+            Language::Finnish => "Finnish",
+            Language::French => "French",
+            Language::German => "German",
+            Language::Greek => "Greek",
+            Language::Hindi => "Hindi",
+            Language::Hungarian => "Hungarian",
+            Language::Italian => "Italian",
+            Language::Japanese => "Japanese",
+            Language::Javanese => "Javanese",
+            Language::Korean => "Korean",
+            Language::Mandarin => "Mandarin",
+            Language::Marathi => "Marathi",
+            Language::Polish => "Polish",
+            Language::Portuguese => "Portuguese",
+            Language::Punjabi => "Punjabi",
+            Language::Romanian => "Romanian",
+            Language::Russian => "Russian",
+            Language::Serbian => "Serbian",
+            Language::Sinhalese => "Sinhalese",
+            Language::Slovak => "Slovak",
+            Language::Spanish => "Spanish",
+            Language::Swedish => "Swedish",
+            Language::Tamil => "Tamil",
+            Language::Telugu => "Telugu",
+            Language::Turkish => "Turkish",
+            Language::Ukrainian => "Ukrainian",
+            Language::Urdu => "Urdu",
+            Language::Vietnamese => "Vietnamese",
+            Language::WuShanghainese => "Wu (Shanghainese)",
+            Language::Xiang => "Xiang",
+            Language::YueCantonese => "Yue (Cantonese)",
+            Language::Zulu => "Zulu"
+        }
+    }
+
+    /// Name of the language in the language itself
+    pub fn native_name(&self) -> &str {
+        match self {
+            Language::Arabic => "العربية",
+            Language::Bengali => "বাংলা",
+            Language::Bulgarian => "Български",
+            Language::ChineseSimplified => "简体中文",
+            Language::ChineseTraditional => "繁體中文",
+            Language::Czech => "Čeština",
+            Language::Danish => "Dansk",
+            Language::Dutch => "Nederlands",
+            Language::Finnish => "Suomi",
+            Language::French => "Français",
+            Language::German => "Deutsch",
+            Language::Greek => "Ελληνικά",
+            Language::Hindi => "हिन्दी",
+            Language::Hungarian => "Magyar",
+            Language::Italian => "Italiano",
+            Language::Japanese => "日本語",
+            Language::Javanese => "Basa Jawa",
+            Language::Korean => "한국어",
+            Language::Mandarin => "普通话",
+            Language::Marathi => "मराठी",
+            Language::Polish => "Polski",
+            Language::Portuguese => "Português",
+            Language::Punjabi => "ਪੰਜਾਬੀ",
+            Language::Romanian => "Română",
+            Language::Russian => "Русский",
+            Language::Serbian => "Српски",
+            Language::Sinhalese => "සිංහල",
+            Language::Slovak => "Slovenčina",
+            Language::Spanish => "Español",
+            Language::Swedish => "Svenska",
+            Language::Tamil => "தமிழ்",
+            Language::Telugu => "తెలుగు",
+            Language::Turkish => "Türkçe",
+            Language::Ukrainian => "Українська",
+            Language::Urdu => "اردو",
+            Language::Vietnamese => "Tiếng Việt",
+            Language::WuShanghainese => "吴语",
+            Language::Xiang => "湘语",
+            Language::YueCantonese => "粵語",
+            Language::Zulu => "isiZulu"
+        }
+    }
+
     pub fn content(&self) -> &str {
         match self {
             Language::Arabic => "ar",
@@ -138,3 +293,34 @@ impl Display for Language {
         write!(f, "{}", self.content())
     }
 }
+
+
+/// Error returned when parsing an unknown [`Language`]
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseLanguageError(String);
+
+impl Display for ParseLanguageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown language `{}`", self.0)
+    }
+}
+
+impl Error for ParseLanguageError {}
+
+impl FromStr for Language {
+    type Err = ParseLanguageError;
+
+    /// Parses a language from its code, its English name or its native name, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let needle = s.trim().to_lowercase();
+        let code = needle.replace('-', "_");
+
+        Self::all()
+            .find(|l| {
+                l.content() == code
+                    || l.name().to_lowercase() == needle
+                    || l.native_name().to_lowercase() == needle
+            })
+            .ok_or_else(|| ParseLanguageError(s.to_string()))
+    }
+}
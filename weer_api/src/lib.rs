@@ -52,7 +52,7 @@
 //! let result = client.history()
 //!     .query(Query::Ip(None))
 //!     .dt(Utc.ymd(2022, 07, 21).and_hms(0, 0, 0))
-//!     .hour()
+//!     .hour(true)
 //!     .call();
 //!
 //! assert!(result.is_ok())
@@ -60,6 +60,9 @@
 //! ```
 
 
+// `ureq::Error` is the public error type of every endpoint.
+#![allow(clippy::result_large_err)]
+
 mod api;
 pub use api::*;
 
@@ -67,22 +70,87 @@ mod models;
 pub use models::*;
 
 mod lang;
-pub use lang::Language;
+pub use lang::{Language, ParseLanguageError};
 
 mod query;
 pub use query::Query;
 
+mod builder;
+pub use builder::ClientBuilder;
+
+mod cache;
+pub use cache::Cache;
+
+mod cassette;
+pub use cassette::Cassette;
+
+mod redact;
+
+mod hash;
+
+mod retry;
+pub use retry::{Retry, RetryPolicy};
+
+mod rate_limit;
+pub use rate_limit::RateLimiter;
+
+mod quota;
+pub use quota::{Quota, Usage, LimitAction, QuotaExceeded};
+
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+
+mod conditions;
+pub use conditions::{ConditionCode, ConditionInfo, ConditionText, Severity, conditions};
+
+mod stats;
+pub use stats::{Stats, Summary, RAINY_DAY_MM, SUNNY_CLOUD_COVER};
+
+mod degree_days;
+pub use degree_days::{DegreeDays, DegreeDay, DegreeDayMethod};
+
+mod agro;
+pub use agro::{GrowingDegreeDays, GrowingDegreeDay, MIN_ET0_HOURS};
+
+mod meteo;
+pub use meteo::{
+    CompassDirection, ParseCompassDirectionError, Beaufort, Comfort,
+    humidex, apparent_temperature, wet_bulb_temperature
+};
+
 pub use chrono;
 
 use ureq::{Agent, AgentBuilder};
-use std::fmt::Display;
+use std::fmt::{self, Display};
 
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Client {
     pub(crate) api_key: String,
     pub(crate) agent: Agent,
-    pub(crate) https: bool
+    pub(crate) https: bool,
+    pub(crate) base_url: Option<String>,
+    pub(crate) cache: Option<Cache>,
+    pub(crate) cassette: Option<Cassette>,
+    pub(crate) retry: RetryPolicy,
+    pub(crate) rate_limiter: Option<RateLimiter>,
+    pub(crate) quota: Option<Quota>
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("api_key", &redact::REDACTED)
+            .field("agent", &self.agent)
+            .field("https", &self.https)
+            .field("base_url", &self.base_url)
+            .field("cache", &self.cache)
+            .field("cassette", &self.cassette)
+            .field("retry", &self.retry)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("quota", &self.quota)
+            .finish()
+    }
 }
 
 impl Client {
@@ -97,25 +165,76 @@ impl Client {
     /// let client = Client::new("api_key", true);
     /// ```
     pub fn new(api_key: &str, https: bool) -> Self {
-        let user_agent = format!(
-            "{name} ({repo} {version})",
-            name = env!("CARGO_PKG_NAME"),
-            repo = env!("CARGO_PKG_REPOSITORY"),
-            version = env!("CARGO_PKG_VERSION"),
-        );
-
         let agent = AgentBuilder::new()
-            .user_agent(&user_agent)
+            .user_agent(&builder::user_agent())
             .https_only(https)
             .build();
 
         Self {
             api_key: api_key.to_string(),
             agent,
-            https
+            https,
+            base_url: None,
+            cache: None,
+            cassette: None,
+            retry: RetryPolicy::default(),
+            rate_limiter: None,
+            quota: None
         }
     }
 
+    /// Builder of a client with timeouts, a proxy or extra root certificates, see [`ClientBuilder`]
+    pub fn builder(api_key: &str) -> ClientBuilder {
+        ClientBuilder::new(api_key)
+    }
+
+    /// Set up the base url of the API
+    ///
+    /// Requests go to `api.weatherapi.com/v1` by default, this points them somewhere else, e.g: to a [`mock::MockServer`].
+    /// Paths such as `forecast.json` are appended to `url`.
+    pub fn base_url(&mut self, url: &str) -> &mut Self {
+        self.base_url = Some(url.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Set up a response cache
+    ///
+    /// Requests to endpoints with a time to live are answered from the cache while the stored response is fresh.
+    pub fn cache(&mut self, cache: Cache) -> &mut Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Set up a cassette to record the requests to, or to replay them from
+    ///
+    /// The cache is bypassed while a cassette is set up. See [`Cassette`].
+    pub fn cassette(&mut self, cassette: Cassette) -> &mut Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
+    /// Set up how failed requests are retried
+    ///
+    /// Defaults to [`RetryPolicy::default`].
+    pub fn retry(&mut self, policy: RetryPolicy) -> &mut Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Set up a rate limiter, requests wait for it before being sent
+    pub fn rate_limit(&mut self, limiter: RateLimiter) -> &mut Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Set up a quota that counts the calls reaching the API and enforces its soft limit
+    ///
+    /// Responses of the cache and of a replaying cassette aren't counted. See [`Quota`].
+    pub fn quota(&mut self, quota: Quota) -> &mut Self {
+        self.quota = Some(quota);
+        self
+    }
+
     /// Function to get forecast
     ///
     /// This returns upto next 14 day weather forecast and weather alert. The data is returned as a Forecast Object.
@@ -133,11 +252,11 @@ impl Client {
     ///
     /// assert!(result.is_ok())
     /// ```
-    pub fn forecast<Tz: chrono::TimeZone>(&self) -> ForecastApi<Tz>
+    pub fn forecast<Tz: chrono::TimeZone>(&self) -> ForecastApi<'_, Tz>
     where
         Tz::Offset: Display
     {
-        ForecastApi::<Tz>::new(&self)
+        ForecastApi::<Tz>::new(self)
     }
 
     /// Function to get future
@@ -157,11 +276,11 @@ impl Client {
     ///
     /// assert!(result.is_ok())
     /// ```
-    pub fn future<Tz: chrono::TimeZone>(&self) -> FutureApi<Tz>
+    pub fn future<Tz: chrono::TimeZone>(&self) -> FutureApi<'_, Tz>
     where
         Tz::Offset: Display
     {
-        FutureApi::new(&self)
+        FutureApi::new(self)
     }
 
     /// Function to get history
@@ -181,11 +300,35 @@ impl Client {
     ///
     /// assert!(result.is_ok())
     /// ```
-    pub fn history<Tz: chrono::TimeZone>(&self) -> HistoryApi<Tz>
+    pub fn history<Tz: chrono::TimeZone>(&self) -> HistoryApi<'_, Tz>
+    where
+        Tz::Offset: Display
+    {
+        HistoryApi::<Tz>::new(self)
+    }
+
+    /// Function to get the history of a range of days
+    ///
+    /// This splits the range from `start` to `end`, both included, into as many history requests as needed and merges
+    /// their days. See [`HistoryRangeApi`].
+    ///
+    /// ## Example:
+    /// ```no_run
+    /// use weer_api::{*, chrono::{Utc, TimeZone}};
+    ///
+    /// let client = Client::new("api_key", true);
+    /// let result = client.history_range(Utc.ymd(2023, 1, 1).and_hms(0, 0, 0), Utc.ymd(2023, 12, 31).and_hms(0, 0, 0))
+    ///     .query(Query::City("London".to_string()))
+    ///     .concurrency(8)
+    ///     .call();
+    ///
+    /// assert_eq!(365, result.unwrap().forecast.forecast_day.len())
+    /// ```
+    pub fn history_range<Tz: chrono::TimeZone>(&self, start: DateTime<Tz>, end: DateTime<Tz>) -> HistoryRangeApi<'_, Tz>
     where
         Tz::Offset: Display
     {
-        HistoryApi::<Tz>::new(&self)
+        HistoryRangeApi::new(self, start, end)
     }
 
     /// Function to get realtime
@@ -204,8 +347,8 @@ impl Client {
     ///
     /// assert!(result.is_ok())
     /// ```
-    pub fn realtime(&self) -> RealtimeApi {
-        RealtimeApi::new(&self)
+    pub fn realtime(&self) -> RealtimeApi<'_> {
+        RealtimeApi::new(self)
     }
 
     /// Function to get realtime
@@ -223,43 +366,61 @@ impl Client {
     ///
     /// assert!(result.is_ok())
     /// ```
-    pub fn search(&self) -> SearchApi {
-        SearchApi::new(&self)
+    pub fn search(&self) -> SearchApi<'_> {
+        SearchApi::new(self)
     }
 
-    pub fn conditions(&self) -> Result<Vec<Condition>, ureq::Error> {
-        Ok(self.agent.get("https://www.weatherapi.com/docs/weather_conditions.json").call()?.into_json()?)
+    /// Weather condition catalogue
+    ///
+    /// The catalogue is bundled with the crate, so this doesn't make any request. See [`conditions`].
+    pub fn conditions(&self) -> &'static [ConditionInfo] {
+        conditions()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::*;
-    use chrono::{Utc, TimeZone};
+    use crate::{*, mock::{fixtures, MockServer}};
+    use chrono::{Utc, TimeZone, NaiveTime};
+
+    fn get_server() -> MockServer {
+        MockServer::start().expect("the mock server needs a local port")
+    }
 
-    fn get_client() -> Client {
-        let api_key = option_env!("API_KEY").unwrap();
-        Client::new(api_key, true)
+    fn error_code(err: ureq::Error) -> (u16, u64) {
+        match err {
+            ureq::Error::Status(status, resp) => {
+                let body: serde_json::Value = resp.into_json().unwrap();
+                (status, body["error"]["code"].as_u64().unwrap())
+            }
+            ureq::Error::Transport(t) => panic!("unexpected transport error: {}", t)
+        }
     }
 
     #[test]
     fn forecast() {
-        let client = get_client();
+        let server = get_server();
+        let client = server.client("api_key");
         let result = client.forecast()
             .query(Query::Ip(None))
-            .dt(Utc.with_ymd_and_hms(2022, 08, 21, 0, 0, 0).earliest().unwrap())
+            .dt(Utc.with_ymd_and_hms(2022, 8, 21, 0, 0, 0).earliest().unwrap())
             .lang(Language::Spanish)
             .call();
 
-        assert!(result.is_ok())
+        assert!(result.is_ok());
+        assert_eq!(
+            vec!["/v1/forecast.json?key=api_key&q=auto%3Aip&dt=2022-08-21&alerts=no&aqi=no&lang=es".to_string()],
+            server.requests()
+        );
     }
 
     #[test]
     fn future() {
-        let client = get_client();
+        let server = get_server();
+        let client = server.client("api_key");
         let result = client.future()
             .query(Query::Ip(None))
-            .dt(Utc.with_ymd_and_hms(2022, 09, 21, 0, 0, 0).earliest().unwrap())
+            .dt(Utc.with_ymd_and_hms(2022, 9, 21, 0, 0, 0).earliest().unwrap())
             .lang(Language::Spanish)
             .call();
 
@@ -268,19 +429,189 @@ mod tests {
 
     #[test]
     fn history() {
-        let client = get_client();
+        let server = get_server();
+        let client = server.client("api_key");
         let result = client.history()
             .query(Query::Ip(None))
-            .dt(Utc.with_ymd_and_hms(2022, 07, 21, 0, 0, 0).earliest().unwrap())
+            .dt(Utc.with_ymd_and_hms(2022, 7, 21, 0, 0, 0).earliest().unwrap())
             .hour(true)
             .call();
 
         assert!(result.is_ok())
     }
 
+    #[test]
+    fn history_range() {
+        let server = get_server();
+        let client = server.client("api_key");
+        let day = |d: u32| Utc.with_ymd_and_hms(2023, 1, d, 0, 0, 0).earliest().unwrap();
+
+        let history = client.history_range(day(1), day(31))
+            .query(Query::City("London".to_string()))
+            .concurrency(3)
+            .call()
+            .unwrap();
+        let dates: Vec<String> = history.forecast.forecast_day.iter().map(|fd| fd.date.clone()).collect();
+        assert_eq!(31, dates.len());
+        assert_eq!(("2023-01-01", "2023-01-31"), (dates[0].as_str(), dates[30].as_str()));
+        assert!(dates.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(31, server.requests().len());
+
+        // A week per request, the last one shorter
+        let history = client.history_range(day(1), day(31))
+            .query(Query::City("London".to_string()))
+            .days_per_call(7)
+            .call()
+            .unwrap();
+        assert_eq!(31, history.forecast.forecast_day.len());
+        let requests = server.requests();
+        assert_eq!(31 + 5, requests.len());
+        assert!(requests.iter().any(|r| r.contains("dt=2023-01-29&end_dt=2023-01-31")));
+
+        assert!(client.history_range(day(2), day(1)).query(Query::Ip(None)).call().is_err());
+        let err = client.history_range(day(1), day(3)).query(Query::City(mock::UNKNOWN_LOCATION.to_string())).call();
+        assert_eq!((400, 1006), error_code(err.unwrap_err()));
+    }
+
+    #[test]
+    fn stats() {
+        let server = get_server();
+        let client = server.client("api_key");
+        let history = client.history_range(
+            Utc.with_ymd_and_hms(2023, 1, 30, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2023, 2, 2, 0, 0, 0).unwrap()
+        )
+            .query(Query::City("London".to_string()))
+            .call()
+            .unwrap();
+
+        let stats = Stats::new(&history.forecast.forecast_day).unwrap();
+        let months: Vec<_> = stats.months.iter().map(|m| (m.start.to_string(), m.end.to_string(), m.days)).collect();
+        assert_eq!(vec![
+            ("2023-01-30".to_string(), "2023-01-31".to_string(), 2),
+            ("2023-02-01".to_string(), "2023-02-02".to_string(), 2)
+        ], months);
+
+        // Every day is the recorded one
+        let period = &stats.period;
+        assert_eq!(4, period.days);
+        assert_eq!((9.0, 4.0, 14.0), (period.mean_temp_c, period.min_temp_c, period.max_temp_c));
+        assert_eq!((42.8, 4), (period.total_precip_mm, period.rainy_days));
+        assert_eq!(85.0, period.mean_humidity);
+        assert_eq!(Some(19.8), period.max_gust_kph);
+        assert_eq!((42.3, Some(0)), (period.daylight_hours, period.sunny_hours));
+        assert_eq!(stats.months[0].mean_temp_c, period.mean_temp_c);
+
+        assert!(Stats::new(&[]).is_none());
+    }
+
+    #[test]
+    fn degree_days() {
+        let server = get_server();
+        let client = server.client("api_key");
+        let history = client.history_range(
+            Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap(),
+            Utc.with_ymd_and_hms(2023, 1, 3, 0, 0, 0).unwrap()
+        )
+            .query(Query::City("London".to_string()))
+            .call()
+            .unwrap();
+
+        // Every day is the recorded one, from 4°C to 14°C
+        let mut degree_days = DegreeDays::new();
+        let days = history.degree_days(&degree_days);
+        assert_eq!(3, days.len());
+        assert_eq!("2023-01-03", days[2].date.to_string());
+        assert_eq!((9.0, 9.0, 0.0), (days[0].temp_c, days[0].hdd, days[0].cdd));
+        assert_eq!((27.0, 0.0), (days[2].cumulative_hdd, days[2].cumulative_cdd));
+
+        // Only the warmest hours are above 12°C
+        degree_days.heating_base(15.5)
+            .cooling_base(12.0)
+            .method(DegreeDayMethod::Hourly);
+        let days = history.degree_days(&degree_days);
+        assert_eq!((9.0, 6.5, 0.4), (days[0].temp_c, days[0].hdd, days[0].cdd));
+        assert_eq!((19.5, 1.2), (days[2].cumulative_hdd, days[2].cumulative_cdd));
+
+        degree_days.method(DegreeDayMethod::Average);
+        assert_eq!((6.5, 0.0), degree_days.day(&history.forecast.forecast_day[0]));
+    }
+
+    #[test]
+    fn growing_degree_days() {
+        let history: History = serde_json::from_str(fixtures::HISTORY).unwrap();
+        let fd = &history.forecast.forecast_day[0];
+
+        // From 4°C to 14°C
+        let mut gdd = GrowingDegreeDays::new();
+        assert_eq!(2.0, gdd.day(fd));
+        assert_eq!(vec![GrowingDegreeDay { date: fd.date(), gdd: 2.0, cumulative_gdd: 2.0 }], history.growing_degree_days(&gdd));
+
+        assert_eq!(3.5, gdd.base(5.0).cap(Some(12.0)).day(fd));
+        assert_eq!(4.5, gdd.cap(None).day(fd));
+        assert_eq!(0.0, gdd.base(15.0).day(fd));
+    }
+
+    #[test]
+    fn derived_meteorology() {
+        assert_eq!(CompassDirection::SSW, CompassDirection::from_degrees(200.0));
+        assert_eq!(CompassDirection::N, CompassDirection::from_degrees(355.0));
+        assert_eq!(CompassDirection::N, CompassDirection::from_degrees(-5.0));
+        assert_eq!(Ok(CompassDirection::WNW), "wnw".parse());
+        assert!("NNN".parse::<CompassDirection>().is_err());
+        assert_eq!(292.5, CompassDirection::WNW.degrees());
+
+        assert_eq!(Beaufort::Calm, Beaufort::from_kph(0.0));
+        assert_eq!((Beaufort::ModerateBreeze, 4), (Beaufort::from_kph(23.4), Beaufort::from_kph(23.4).number()));
+        assert_eq!(Beaufort::HurricaneForce, Beaufort::from_kph(150.0));
+
+        assert_eq!(41.0, humidex(30.0, 70.0));
+        assert_eq!(24.1, apparent_temperature(25.0, 50.0, 10.8));
+        assert_eq!(13.7, wet_bulb_temperature(20.0, 50.0));
+
+        assert_eq!(Comfort::GreatDiscomfort, Comfort::new(30.0, 70.0, 0.0));
+        assert_eq!(Comfort::Comfortable, Comfort::new(22.0, 50.0, 5.0));
+        assert_eq!(Comfort::Freezing, Comfort::new(2.0, 80.0, 30.0));
+
+        // Both models give the same values
+        let realtime: Realtime = serde_json::from_str(fixtures::CURRENT).unwrap();
+        let history: History = serde_json::from_str(fixtures::HISTORY).unwrap();
+        let hour = &history.forecast.forecast_day[0].hour[0];
+        assert_eq!(CompassDirection::SSW, realtime.current.wind_direction());
+        assert_eq!(CompassDirection::SSW, hour.wind_direction());
+        assert_eq!(CompassDirection::from_degrees(hour.wind_degree), hour.wind_direction());
+        assert_eq!(
+            (hour.humidex(), hour.comfort()),
+            (humidex(hour.temp_c, hour.humidity as f32), Comfort::new(hour.temp_c, hour.humidity as f32, hour.wind_kph))
+        );
+    }
+
+    #[test]
+    fn reference_evapotranspiration() {
+        // FAO-56 example 19, N'Diaye in Senegal on the 1st of October from 14:00 to 15:00 local time
+        let coords = Coords { lat: 16.217, lon: -16.25 };
+        let ra = agro::extraterrestrial_radiation(Utc.with_ymd_and_hms(2023, 10, 1, 15, 30, 0).unwrap(), &coords);
+        assert!((ra - 3.543).abs() < 0.01, "{}", ra);
+        let et0 = agro::penman_monteith(38.0, 52.0, 3.3, 101.2, 2.450, 2.450 / 2.658);
+        assert!((et0 - 0.63).abs() < 0.005, "{}", et0);
+
+        // No sun at midnight
+        let night = Utc.with_ymd_and_hms(2023, 10, 1, 1, 0, 0).unwrap();
+        assert_eq!(0.0, agro::extraterrestrial_radiation(night, &coords));
+
+        let history: History = serde_json::from_str(fixtures::HISTORY).unwrap();
+        let mut fd = history.forecast.forecast_day.into_iter().next().unwrap();
+        let et0 = fd.reference_evapotranspiration(&history.location.coords).unwrap();
+        assert!((0.5..3.0).contains(&et0), "{}", et0);
+
+        fd.hour.truncate(1);
+        assert_eq!(None, fd.reference_evapotranspiration(&history.location.coords));
+    }
+
     #[test]
     fn realtime() {
-        let client = get_client();
+        let server = get_server();
+        let client = server.client("api_key");
         let result = client.realtime()
             .query(Query::Ip(None))
             .lang(Language::Spanish)
@@ -291,12 +622,350 @@ mod tests {
 
     #[test]
     fn search() {
-        let client = get_client();
+        let server = get_server();
+        let client = server.client("api_key");
         let result = client.search().query(Query::Ip(None)).call();
 
         assert!(result.is_ok())
     }
 
+    #[test]
+    fn api_errors() {
+        let server = get_server();
+        let london = || Query::City("London".to_string());
+
+        let err = server.client("api_key").realtime().query(Query::City(mock::UNKNOWN_LOCATION.to_string())).call();
+        assert_eq!((400, 1006), error_code(err.unwrap_err()));
+
+        let err = server.client(mock::INVALID_KEY).realtime().query(london()).call();
+        assert_eq!((401, 2006), error_code(err.unwrap_err()));
+
+        let err = server.client(mock::OVER_QUOTA_KEY).search().query(london()).call();
+        assert_eq!((403, 2007), error_code(err.unwrap_err()));
+    }
+
+    #[test]
+    fn cassette() {
+        let path = std::env::temp_dir().join(format!("weer-cassette-{}.json", std::process::id()));
+        let london = || Query::City("London".to_string());
+        let nowhere = || Query::City(mock::UNKNOWN_LOCATION.to_string());
+
+        let server = get_server();
+        let mut client = server.client("secret");
+        client.cassette(Cassette::record(&path));
+        assert!(client.realtime().query(london()).call().is_ok());
+        assert!(client.realtime().query(nowhere()).call().is_err());
+        drop(server);
+
+        let recorded = std::fs::read_to_string(&path).unwrap();
+        assert!(recorded.contains("<redacted>") && !recorded.contains("secret"));
+
+        // Nothing listens on the discard port, every answer comes from the cassette
+        let mut client = Client::new("other", false);
+        client.base_url("http://127.0.0.1:9/v1");
+        client.cassette(Cassette::replay(&path).unwrap());
+        assert_eq!("London", client.realtime().query(london()).call().unwrap().location.name);
+        assert_eq!("London", client.realtime().query(london()).call().unwrap().location.name);
+        assert_eq!((400, 1006), error_code(client.realtime().query(nowhere()).call().unwrap_err()));
+        assert!(matches!(client.search().query(london()).call(), Err(ureq::Error::Transport(_))));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn retries() {
+        use std::{sync::{Arc, atomic::{AtomicU32, Ordering}}, time::Duration};
+
+        let retries = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&retries);
+        let mut policy = RetryPolicy::default();
+        policy.base_delay(Duration::from_millis(1))
+            .on_retry(move |retry| {
+                assert!(retry.delay <= Duration::from_millis(1 << retry.attempt));
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+
+        let server = get_server();
+        let mut client = server.client(mock::FLAKY_KEY);
+        client.retry(policy.clone());
+        assert!(client.search().query(Query::Ip(None)).call().is_ok());
+        assert_eq!(mock::FLAKY_FAILURES as u32, retries.load(Ordering::SeqCst));
+
+        let server = get_server();
+        let mut client = server.client(mock::FLAKY_KEY);
+        client.retry(policy.max_attempts(2).clone());
+        assert_eq!((503, 9999), error_code(client.search().query(Query::Ip(None)).call().unwrap_err()));
+        assert_eq!(2, server.requests().len());
+        assert_eq!(mock::FLAKY_FAILURES as u32 + 1, retries.load(Ordering::SeqCst));
+
+        // Only the configured statuses are retried
+        let server = get_server();
+        let mut client = server.client(mock::INVALID_KEY);
+        client.retry(policy.max_attempts(5).clone());
+        assert!(client.search().query(Query::Ip(None)).call().is_err());
+        assert_eq!(1, server.requests().len());
+    }
+
+    #[test]
+    fn retry_delays() {
+        use std::time::Duration;
+
+        let status = |status, retry_after: &str| {
+            let resp = format!("HTTP/1.1 {} Error\r\nretry-after: {}\r\n\r\n", status, retry_after);
+            ureq::Error::Status(status, resp.parse().unwrap())
+        };
+
+        let mut policy = RetryPolicy::default();
+        policy.max_attempts(10).jitter(false).max_delay(Duration::from_secs(10));
+        assert_eq!(Some(Duration::from_secs(1)), policy.delay(1, &status(503, "")));
+        assert_eq!(Some(Duration::from_secs(8)), policy.delay(4, &status(503, "")));
+        assert_eq!(Some(Duration::from_secs(10)), policy.delay(5, &status(503, "")));
+        assert_eq!(Some(Duration::from_secs(3)), policy.delay(1, &status(429, "3")));
+        assert_eq!(Some(Duration::from_secs(10)), policy.delay(1, &status(429, "3600")));
+        assert_eq!(None, policy.delay(1, &status(500, "")));
+        assert_eq!(None, policy.delay(10, &status(503, "")));
+
+        policy.jitter(true);
+        for _ in 0..20 {
+            let delay = policy.delay(2, &status(503, "")).unwrap();
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+
+        assert_eq!(None, RetryPolicy::never().delay(1, &status(503, "")));
+    }
+
+    #[test]
+    fn rate_limit() {
+        use std::time::{Duration, Instant};
+
+        let server = get_server();
+        let mut client = server.client("api_key");
+        client.rate_limit(RateLimiter::new(2, Duration::from_millis(200)));
+
+        let start = Instant::now();
+        for _ in 0..4 {
+            client.search().query(Query::Ip(None)).call().unwrap();
+        }
+        // Two calls come out of the full bucket, the next two wait for a token each
+        assert!(start.elapsed() >= Duration::from_millis(180));
+    }
+
+    #[test]
+    fn quota() {
+        let path = std::env::temp_dir().join(format!("weer-quota-{}.json", std::process::id()));
+        let server = get_server();
+        let mut quota = Quota::new(&path);
+        quota.budget(10).soft_limit(30);
+
+        let mut client = server.client("api_key");
+        client.quota(quota.clone());
+        for _ in 0..3 {
+            client.search().query(Query::Ip(None)).call().unwrap();
+        }
+        server.client("other").search().query(Query::Ip(None)).call().unwrap();
+
+        let usage = quota.usage("api_key").unwrap();
+        assert_eq!((3, Some(3)), (usage.calls, usage.soft_limit));
+        assert!(usage.is_limited());
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("api_key"));
+
+        // Refused without reaching the server
+        let err = client.search().query(Query::Ip(None)).call().unwrap_err();
+        let ureq::Error::Transport(t) = err else { panic!("expected a transport error") };
+        let source = std::error::Error::source(&t).and_then(|s| s.downcast_ref::<std::io::Error>());
+        assert!(source.and_then(|e| e.get_ref()).is_some_and(|e| e.is::<QuotaExceeded>()));
+        assert_eq!(4, server.requests().len());
+
+        // A new period starts from zero
+        assert_eq!(0, quota.usage_on("api_key", usage.next_reset).unwrap().calls);
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(path.with_extension("lock")).unwrap();
+    }
+
+    #[test]
+    fn concurrent_quota() {
+        let path = std::env::temp_dir().join(format!("weer-quota-concurrent-{}.json", std::process::id()));
+        let quota = Quota::new(&path);
+
+        std::thread::scope(|s| {
+            for _ in 0..8 {
+                let quota = quota.clone();
+                s.spawn(move || (0..25).for_each(|_| quota.count("api_key").unwrap()));
+            }
+            // A quota of its own, as another process would have
+            s.spawn(|| (0..25).for_each(|_| Quota::new(&path).count("api_key").unwrap()));
+        });
+
+        assert_eq!(225, quota.usage("api_key").unwrap().calls);
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(path.with_extension("lock")).unwrap();
+    }
+
+    #[test]
+    fn stable_hash() {
+        assert_eq!(0xcbf29ce484222325, hash::fnv1a(b""));
+        assert_eq!(0xaf63dc4c8601ec8c, hash::fnv1a(b"a"));
+        assert_eq!(0x85944171f73967e8, hash::fnv1a(b"foobar"));
+    }
+
+    #[test]
+    fn billing_periods() {
+        use quota::period;
+        let date = |s: &str| s.parse::<chrono::NaiveDate>().unwrap();
+
+        assert_eq!((date("2026-10-01"), date("2026-11-01")), period(date("2026-10-19"), 1));
+        assert_eq!((date("2026-09-20"), date("2026-10-20")), period(date("2026-10-19"), 20));
+        assert_eq!((date("2026-10-19"), date("2026-11-19")), period(date("2026-10-19"), 19));
+        assert_eq!((date("2026-01-31"), date("2026-02-28")), period(date("2026-02-15"), 31));
+        assert_eq!((date("2026-02-28"), date("2026-03-31")), period(date("2026-03-01"), 31));
+        assert_eq!((date("2025-12-15"), date("2026-01-15")), period(date("2026-01-02"), 15));
+    }
+
+    #[test]
+    fn client_builder() {
+        use std::time::Duration;
+
+        let server = get_server();
+        let proxy = server.url().replace("/v1", "");
+
+        // The mock server stands in for the proxy of an unreachable host
+        let client = Client::builder("api_key")
+            .https(false)
+            .base_url("http://weather.invalid/v1")
+            .timeout_connect(Duration::from_secs(5))
+            .timeout_read(Duration::from_secs(5))
+            .proxy(&proxy)
+            .proxy_from_env(false)
+            .build()
+            .unwrap();
+        client.search().query(Query::Ip(None)).call().unwrap();
+        assert!(server.requests()[0].starts_with("http://weather.invalid/v1/search.json?"));
+
+        // Hosts of the no_proxy list are reached directly, the proxy isn't even listening
+        let client = Client::builder("api_key")
+            .https(false)
+            .base_url(&server.url())
+            .proxy("http://127.0.0.1:9")
+            .no_proxy(&["localhost", "127.0.0.1"])
+            .build()
+            .unwrap();
+        client.search().query(Query::Ip(None)).call().unwrap();
+        assert!(server.requests()[1].starts_with("/v1/search.json?"));
+
+        assert!(Client::builder("api_key").proxy("ftp://proxy.example.com").build().is_err());
+        assert!(Client::builder("api_key").root_certificates(b"not a certificate").build().is_err());
+    }
+
+    #[test]
+    fn key_redaction() {
+        let server = get_server();
+
+        let err = server.client(mock::INVALID_KEY).search().query(Query::Ip(None)).call().unwrap_err();
+        assert!(!err.to_string().contains("key=invalid"));
+        assert_eq!((401, 2006), error_code(err));
+
+        let mut client = Client::new("s3cr3t", false);
+        client.base_url("http://127.0.0.1:9/v1").retry(RetryPolicy::never());
+        let err = client.search().query(Query::Ip(None)).call().unwrap_err();
+        assert!(matches!(err, ureq::Error::Transport(_)));
+        assert!(!err.to_string().contains("s3cr3t"));
+
+        assert!(!format!("{:?}", client).contains("s3cr3t"));
+        assert!(!format!("{:?}", Client::builder("s3cr3t")).contains("s3cr3t"));
+    }
+
+    #[test]
+    fn no_proxy() {
+        use builder::bypasses_proxy;
+        let list = |s: &str| s.split(',').map(str::to_string).collect::<Vec<_>>();
+
+        assert!(bypasses_proxy("api.weatherapi.com", &list("localhost, .weatherapi.com")));
+        assert!(bypasses_proxy("api.weatherapi.com", &list("*.weatherapi.com")));
+        assert!(bypasses_proxy("API.weatherapi.com", &list("api.weatherapi.com")));
+        assert!(bypasses_proxy("[::1]", &list("::1")));
+        assert!(bypasses_proxy("api.weatherapi.com", &list("*")));
+        assert!(!bypasses_proxy("api.weatherapi.com", &list("apiweatherapi.com,")));
+        assert!(!bypasses_proxy("api.weatherapi.com", &[]));
+    }
+
+    #[test]
+    fn pem_certificates() {
+        use builder::parse_certificates;
+
+        let pem = "-----BEGIN CERTIFICATE-----\nAAEC\nAw==\n-----END CERTIFICATE-----\n\
+            -----BEGIN CERTIFICATE-----\nBAUG\n-----END CERTIFICATE-----\n";
+        assert_eq!(vec![vec![0, 1, 2, 3], vec![4, 5, 6]], parse_certificates(pem.as_bytes()).unwrap());
+        assert_eq!(vec![vec![0x30, 0x82]], parse_certificates(&[0x30, 0x82]).unwrap());
+        assert!(parse_certificates(b"-----BEGIN CERTIFICATE-----\nAAEC\n").is_err());
+    }
+
+    #[test]
+    fn realtime_model() {
+        let realtime: Realtime = serde_json::from_str(fixtures::CURRENT).unwrap();
+        assert_eq!("Europe/London", realtime.location.tz_id.as_deref().unwrap());
+        assert_eq!(ConditionCode::LightRain, realtime.current.condition.code);
+        assert_eq!(200.0, realtime.current.wind_degree);
+        assert!(realtime.current.is_day());
+        assert_eq!(1, realtime.current.air_quality.unwrap().us_epa_index);
+    }
+
+    #[test]
+    fn forecast_model() {
+        let forecast: Forecast = serde_json::from_str(fixtures::FORECAST).unwrap();
+        let days = &forecast.forecast.forecast_day;
+        assert_eq!(2, days.len());
+        assert!(days.iter().all(|fd| fd.hour.len() == 24));
+
+        let today = &days[0];
+        assert_eq!(Some(87), today.day.daily_chance_of_rain);
+        assert!(today.day.air_quality.is_some());
+        assert!(today.hour[0].air_quality.is_some());
+        assert_eq!(MoonPhase::WaxingCrescent, today.astro.moon_phase);
+
+        let alerts = forecast.alerts.unwrap().alert;
+        assert_eq!(1, alerts.len());
+        assert_eq!("Yellow Warning for Rain", alerts[0].event);
+        assert!(alerts[0].effective() < alerts[0].expires());
+    }
+
+    #[test]
+    fn history_model() {
+        let history: History = serde_json::from_str(fixtures::HISTORY).unwrap();
+        let day = &history.forecast.forecast_day[0];
+        assert_eq!("2023-10-18", day.date);
+        assert_eq!(None, day.day.daily_chance_of_rain);
+        assert!(day.hour.iter().all(|h| h.air_quality.is_none()));
+
+        let future: Future = serde_json::from_str(fixtures::FUTURE).unwrap();
+        assert_eq!(8, future.forecast.forecast_day[0].hour.len());
+    }
+
+    #[test]
+    fn search_model() {
+        let locations: Vec<Location> = serde_json::from_str(fixtures::SEARCH).unwrap();
+        assert_eq!(2, locations.len());
+        assert_eq!(Some(2801268), locations[0].id);
+        assert_eq!(None, locations[0].localtime);
+        assert_eq!("51.52, -0.11", locations[0].coords.to_string());
+    }
+
+    #[test]
+    fn astro_times() {
+        let forecast: Forecast = serde_json::from_str(fixtures::FORECAST).unwrap();
+        let days = &forecast.forecast.forecast_day;
+
+        let astro = &days[0].astro;
+        assert_eq!(NaiveTime::from_hms_opt(7, 26, 0), astro.sunrise());
+        assert_eq!(NaiveTime::from_hms_opt(18, 0, 0), astro.sunset());
+        assert_eq!(NaiveTime::from_hms_opt(11, 48, 0), astro.moonrise());
+        assert_eq!(NaiveTime::from_hms_opt(19, 26, 0), astro.moonset());
+
+        assert_eq!("No moonrise", days[1].astro.moonrise);
+        assert_eq!(None, days[1].astro.moonrise());
+    }
+
     #[test]
     fn lang() {
         #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -310,6 +979,79 @@ mod tests {
         assert!(serde_json::to_string(&m).is_ok());
     }
 
+    #[test]
+    fn lang_from_str() {
+        assert_eq!(Ok(Language::Spanish), "es".parse());
+        assert_eq!(Ok(Language::ChineseTraditional), "zh-TW".parse());
+        assert_eq!(Ok(Language::German), "german".parse());
+        assert_eq!(Ok(Language::Russian), "Русский".parse());
+        assert!("klingon".parse::<Language>().is_err());
+
+        for lang in Language::all() {
+            assert_eq!(Ok(lang), lang.content().parse());
+            assert_eq!(Ok(lang), lang.native_name().parse());
+        }
+    }
+
+    #[test]
+    fn lang_from_locale() {
+        assert_eq!(Some(Language::Spanish), Language::from_locale("es_AR.UTF-8"));
+        assert_eq!(Some(Language::ChineseTraditional), Language::from_locale("zh_TW.UTF-8"));
+        assert_eq!(Some(Language::ChineseSimplified), Language::from_locale("zh_CN"));
+        assert_eq!(Some(Language::Serbian), Language::from_locale("sr_RS@latin"));
+        assert_eq!(None, Language::from_locale("en_US.UTF-8"));
+        assert_eq!(None, Language::from_locale("C"));
+    }
+
+    #[test]
+    fn condition_code() {
+        let c: Condition = serde_json::from_str(r#"{"text": "Sunny", "icon": "", "code": 1000}"#).unwrap();
+        assert_eq!(ConditionCode::Sunny, c.code);
+        assert_eq!(r#"{"text":"Sunny","icon":"","code":1000}"#, serde_json::to_string(&c).unwrap());
+
+        let c: Condition = serde_json::from_str(r#"{"text": "", "icon": "", "code": 4242}"#).unwrap();
+        assert_eq!(ConditionCode::Unknown(4242), c.code);
+
+        assert!(ConditionCode::HeavySnow.is_snow() && ConditionCode::HeavySnow.is_precipitation());
+        assert!(ConditionCode::PatchyLightRainWithThunder.is_thunder());
+        assert!(ConditionCode::FreezingFog.is_fog() && !ConditionCode::FreezingFog.is_precipitation());
+        assert!(ConditionCode::Blizzard > ConditionCode::LightSnow);
+        assert!(ConditionCode::Sunny < ConditionCode::Overcast);
+    }
+
+    #[test]
+    fn condition_catalogue() {
+        assert_eq!(ConditionCode::ALL.len(), conditions().len());
+
+        for code in ConditionCode::ALL {
+            assert_eq!(code, ConditionCode::from_code(code.code()));
+            assert!(code.info().is_some(), "{:?} is missing from the catalogue", code);
+        }
+
+        for info in conditions() {
+            for t in &info.languages {
+                assert!(
+                    Language::all().any(|lang| t.lang_iso == lang.content()),
+                    "{:?} has a text in unknown language {}", info.code, t.lang_iso
+                );
+            }
+        }
+
+        let mut sunny = ConditionCode::Sunny.info().unwrap().clone();
+        assert_eq!("Clear", sunny.text(false, Some(Language::Zulu)));
+
+        sunny.languages.push(ConditionText {
+            lang_name: "Spanish".to_string(),
+            lang_iso: "es".to_string(),
+            day_text: "Soleado".to_string(),
+            night_text: "Despejado".to_string()
+        });
+        assert_eq!("Soleado", sunny.text(true, Some(Language::Spanish)));
+        assert_eq!("Despejado", sunny.text(false, Some(Language::Spanish)));
+        assert_eq!("Clear", sunny.text(false, Some(Language::Zulu)));
+        assert_eq!("//cdn.weatherapi.com/weather/64x64/day/113.png", sunny.icon(true));
+    }
+
     #[test]
     fn it_works() {
         let result = 2 + 2;
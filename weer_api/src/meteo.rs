@@ -0,0 +1,284 @@
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    str::FromStr
+};
+use serde::{Serialize, Deserialize};
+
+use crate::{Current, Hour, stats::round};
+
+
+/// One of the 16 points of the compass, as in `wind_dir`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CompassDirection {
+    N, NNE, NE, ENE,
+    E, ESE, SE, SSE,
+    S, SSW, SW, WSW,
+    W, WNW, NW, NNW
+}
+
+impl CompassDirection {
+    /// Every point, clockwise from north
+    pub const ALL: [CompassDirection; 16] = [
+        Self::N, Self::NNE, Self::NE, Self::ENE,
+        Self::E, Self::ESE, Self::SE, Self::SSE,
+        Self::S, Self::SSW, Self::SW, Self::WSW,
+        Self::W, Self::WNW, Self::NW, Self::NNW
+    ];
+
+    /// Point whose sector holds `degrees`, measured clockwise from north
+    pub fn from_degrees(degrees: f32) -> Self {
+        let sector = (degrees.rem_euclid(360.0) / 22.5).round() as usize;
+        Self::ALL[sector % 16]
+    }
+
+    /// Centre of the sector of the point, in degrees
+    pub fn degrees(&self) -> f32 {
+        Self::ALL.iter().position(|d| d == self).unwrap_or(0) as f32 * 22.5
+    }
+
+    /// Abbreviation the API uses, e.g: `SSW`
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            Self::N => "N", Self::NNE => "NNE", Self::NE => "NE", Self::ENE => "ENE",
+            Self::E => "E", Self::ESE => "ESE", Self::SE => "SE", Self::SSE => "SSE",
+            Self::S => "S", Self::SSW => "SSW", Self::SW => "SW", Self::WSW => "WSW",
+            Self::W => "W", Self::WNW => "WNW", Self::NW => "NW", Self::NNW => "NNW"
+        }
+    }
+}
+
+impl Display for CompassDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.abbreviation())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCompassDirectionError(String);
+
+impl Display for ParseCompassDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown compass direction `{}`", self.0)
+    }
+}
+
+impl Error for ParseCompassDirectionError {}
+
+impl FromStr for CompassDirection {
+    type Err = ParseCompassDirectionError;
+
+    /// Parses an abbreviation, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let needle = s.trim().to_uppercase();
+
+        Self::ALL.into_iter()
+            .find(|d| d.abbreviation() == needle)
+            .ok_or_else(|| ParseCompassDirectionError(s.to_string()))
+    }
+}
+
+
+/// Beaufort wind force, from the mean wind speed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Beaufort {
+    Calm,
+    LightAir,
+    LightBreeze,
+    GentleBreeze,
+    ModerateBreeze,
+    FreshBreeze,
+    StrongBreeze,
+    NearGale,
+    Gale,
+    StrongGale,
+    Storm,
+    ViolentStorm,
+    HurricaneForce
+}
+
+impl Beaufort {
+    /// Lowest speed of each force from 1 up, in m/s
+    const THRESHOLDS: [f32; 12] = [0.5, 1.6, 3.4, 5.5, 8.0, 10.8, 13.9, 17.2, 20.8, 24.5, 28.5, 32.7];
+
+    const ALL: [Beaufort; 13] = [
+        Self::Calm, Self::LightAir, Self::LightBreeze, Self::GentleBreeze, Self::ModerateBreeze, Self::FreshBreeze,
+        Self::StrongBreeze, Self::NearGale, Self::Gale, Self::StrongGale, Self::Storm, Self::ViolentStorm,
+        Self::HurricaneForce
+    ];
+
+    /// Force of a wind speed in km/h
+    pub fn from_kph(kph: f32) -> Self {
+        let force = Self::THRESHOLDS.iter().take_while(|min| kph / 3.6 >= **min).count();
+        Self::ALL[force]
+    }
+
+    /// Force number, from 0 to 12
+    pub fn number(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl Display for Beaufort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Calm => write!(f, "Calm"),
+            Self::LightAir => write!(f, "Light air"),
+            Self::LightBreeze => write!(f, "Light breeze"),
+            Self::GentleBreeze => write!(f, "Gentle breeze"),
+            Self::ModerateBreeze => write!(f, "Moderate breeze"),
+            Self::FreshBreeze => write!(f, "Fresh breeze"),
+            Self::StrongBreeze => write!(f, "Strong breeze"),
+            Self::NearGale => write!(f, "Near gale"),
+            Self::Gale => write!(f, "Gale"),
+            Self::StrongGale => write!(f, "Strong gale"),
+            Self::Storm => write!(f, "Storm"),
+            Self::ViolentStorm => write!(f, "Violent storm"),
+            Self::HurricaneForce => write!(f, "Hurricane force")
+        }
+    }
+}
+
+
+/// How the weather feels, from the coldest to the most dangerous heat
+///
+/// Heat follows the humidex ranges of Environment Canada: some discomfort from 30, great discomfort from 40, danger
+/// from 46. Below that, the apparent temperature is freezing under 0°C, cold under 10°C and cool under 18°C.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Comfort {
+    Freezing,
+    Cold,
+    Cool,
+    Comfortable,
+    SomeDiscomfort,
+    GreatDiscomfort,
+    Dangerous
+}
+
+impl Comfort {
+    /// Category of a temperature in celsius, relative humidity as percentage and wind speed in km/h
+    pub fn new(temp_c: f32, humidity: f32, wind_kph: f32) -> Self {
+        match (humidex(temp_c, humidity), apparent_temperature(temp_c, humidity, wind_kph)) {
+            (h, _) if h >= 46.0 => Self::Dangerous,
+            (h, _) if h >= 40.0 => Self::GreatDiscomfort,
+            (h, _) if h >= 30.0 => Self::SomeDiscomfort,
+            (_, at) if at < 0.0 => Self::Freezing,
+            (_, at) if at < 10.0 => Self::Cold,
+            (_, at) if at < 18.0 => Self::Cool,
+            _ => Self::Comfortable
+        }
+    }
+}
+
+impl Display for Comfort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Freezing => write!(f, "Freezing"),
+            Self::Cold => write!(f, "Cold"),
+            Self::Cool => write!(f, "Cool"),
+            Self::Comfortable => write!(f, "Comfortable"),
+            Self::SomeDiscomfort => write!(f, "Some discomfort"),
+            Self::GreatDiscomfort => write!(f, "Great discomfort"),
+            Self::Dangerous => write!(f, "Dangerous")
+        }
+    }
+}
+
+
+/// Humidex of a temperature in celsius and a relative humidity as percentage
+pub fn humidex(temp_c: f32, humidity: f32) -> f32 {
+    round(temp_c + 0.5555 * (vapour_pressure(temp_c, humidity) - 10.0))
+}
+
+/// Apparent temperature of Steadman, in celsius, without the solar radiation
+///
+/// The version of the Australian Bureau of Meteorology, with the wind speed in km/h at 10 metres.
+pub fn apparent_temperature(temp_c: f32, humidity: f32, wind_kph: f32) -> f32 {
+    round(temp_c + 0.33 * vapour_pressure(temp_c, humidity) - 0.70 * wind_kph / 3.6 - 4.00)
+}
+
+/// Wet-bulb temperature, in celsius, with the formula of Stull (2011)
+///
+/// Valid from 5% to 99% of relative humidity and from -20°C to 50°C.
+pub fn wet_bulb_temperature(temp_c: f32, humidity: f32) -> f32 {
+    let (t, rh) = (temp_c, humidity);
+
+    round(
+        t * (0.151977 * (rh + 8.313659).sqrt()).atan()
+        + (t + rh).atan()
+        - (rh - 1.676331).atan()
+        + 0.00391838 * rh.powf(1.5) * (0.023101 * rh).atan()
+        - 4.686035
+    )
+}
+
+/// Water vapour pressure in hPa, from the Magnus formula
+fn vapour_pressure(temp_c: f32, humidity: f32) -> f32 {
+    6.112 * (17.67 * temp_c / (temp_c + 243.5)).exp() * humidity / 100.0
+}
+
+
+impl Current {
+    /// Point of the compass the wind blows from, from `wind_dir` or else `wind_degree`
+    pub fn wind_direction(&self) -> CompassDirection {
+        self.wind_dir.parse().unwrap_or_else(|_| CompassDirection::from_degrees(self.wind_degree))
+    }
+
+    /// See [`Beaufort`]
+    pub fn beaufort(&self) -> Beaufort {
+        Beaufort::from_kph(self.wind_kph)
+    }
+
+    /// See [`humidex`]
+    pub fn humidex(&self) -> f32 {
+        humidex(self.temp_c, self.humidity as f32)
+    }
+
+    /// See [`apparent_temperature`]
+    pub fn apparent_temperature(&self) -> f32 {
+        apparent_temperature(self.temp_c, self.humidity as f32, self.wind_kph)
+    }
+
+    /// See [`wet_bulb_temperature`]
+    pub fn wet_bulb_temperature(&self) -> f32 {
+        wet_bulb_temperature(self.temp_c, self.humidity as f32)
+    }
+
+    /// See [`Comfort`]
+    pub fn comfort(&self) -> Comfort {
+        Comfort::new(self.temp_c, self.humidity as f32, self.wind_kph)
+    }
+}
+
+impl Hour {
+    /// Point of the compass the wind blows from, from `wind_dir` or else `wind_degree`
+    pub fn wind_direction(&self) -> CompassDirection {
+        self.wind_dir.parse().unwrap_or_else(|_| CompassDirection::from_degrees(self.wind_degree))
+    }
+
+    /// See [`Beaufort`]
+    pub fn beaufort(&self) -> Beaufort {
+        Beaufort::from_kph(self.wind_kph)
+    }
+
+    /// See [`humidex`]
+    pub fn humidex(&self) -> f32 {
+        humidex(self.temp_c, self.humidity as f32)
+    }
+
+    /// See [`apparent_temperature`]
+    pub fn apparent_temperature(&self) -> f32 {
+        apparent_temperature(self.temp_c, self.humidity as f32, self.wind_kph)
+    }
+
+    /// See [`wet_bulb_temperature`]
+    pub fn wet_bulb_temperature(&self) -> f32 {
+        wet_bulb_temperature(self.temp_c, self.humidity as f32)
+    }
+
+    /// See [`Comfort`]
+    pub fn comfort(&self) -> Comfort {
+        Comfort::new(self.temp_c, self.humidity as f32, self.wind_kph)
+    }
+}
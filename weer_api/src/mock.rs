@@ -0,0 +1,235 @@
+//! Local stand-in for Weather API, enabled by the `mock` feature
+//!
+//! [`MockServer`] answers every endpoint with a response recorded in the `fixtures` directory, so the tests of this
+//! crate and of the crates using it run offline and without an api key.
+//!
+//! # Example:
+//! ```
+//! use weer_api::{*, mock::MockServer};
+//!
+//! let server = MockServer::start().unwrap();
+//! let client = server.client("api_key");
+//!
+//! let realtime = client.realtime().query(Query::City("London".to_string())).call().unwrap();
+//! assert_eq!("London", realtime.location.name);
+//! ```
+
+use std::{
+    borrow::Cow,
+    io::{self, BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}},
+    thread
+};
+use url::Url;
+
+use crate::Client;
+
+
+/// Recorded responses, as sent by Weather API
+pub mod fixtures {
+    /// Realtime weather of London with air quality
+    pub const CURRENT: &str = include_str!("../fixtures/current.json");
+    /// Two days of forecast for London with alerts and air quality, the second day has no moonrise
+    pub const FORECAST: &str = include_str!("../fixtures/forecast.json");
+    /// A day of history for London
+    pub const HISTORY: &str = include_str!("../fixtures/history.json");
+    /// A day of future weather for London, every 3 hours
+    pub const FUTURE: &str = include_str!("../fixtures/future.json");
+    /// Locations matching London
+    pub const SEARCH: &str = include_str!("../fixtures/search.json");
+    /// Error 1005, sent with a 400 for an unknown endpoint
+    pub const INVALID_URL: &str = include_str!("../fixtures/error-1005.json");
+    /// Error 1006, sent with a 400 for a location that doesn't exist
+    pub const NO_LOCATION: &str = include_str!("../fixtures/error-1006.json");
+    /// Error 2006, sent with a 401 for an invalid api key
+    pub const INVALID_KEY: &str = include_str!("../fixtures/error-2006.json");
+    /// Error 2007, sent with a 403 once the monthly quota is exceeded
+    pub const QUOTA_EXCEEDED: &str = include_str!("../fixtures/error-2007.json");
+    /// Error 9999, sent when the API fails
+    pub const INTERNAL_ERROR: &str = include_str!("../fixtures/error-9999.json");
+}
+
+/// Api key the server rejects
+pub const INVALID_KEY: &str = "invalid";
+
+/// Api key whose quota is exceeded
+pub const OVER_QUOTA_KEY: &str = "over-quota";
+
+/// Api key whose first [`FLAKY_FAILURES`] requests are answered with a 503
+pub const FLAKY_KEY: &str = "flaky";
+
+pub const FLAKY_FAILURES: usize = 2;
+
+/// Location the server doesn't find
+pub const UNKNOWN_LOCATION: &str = "Nowhere";
+
+/// HTTP server on a free local port, stopped when dropped
+pub struct MockServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<String>>>,
+    stop: Arc<AtomicBool>
+}
+
+impl MockServer {
+    pub fn start() -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let (log, stopped) = (Arc::clone(&requests), Arc::clone(&stop));
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stopped.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    // A client that hangs up early only fails its own request
+                    let _ = serve(stream, &log);
+                }
+            }
+        });
+
+        Ok(Self { addr, requests, stop })
+    }
+
+    /// Base url to give to [`Client::base_url`]
+    pub fn url(&self) -> String {
+        format!("http://{}/v1", self.addr)
+    }
+
+    /// Client sending its requests to this server
+    pub fn client(&self, api_key: &str) -> Client {
+        let mut client = Client::new(api_key, false);
+        client.base_url(&self.url());
+        client
+    }
+
+    /// Path and query of every request received so far, the whole url of the ones it got as a proxy
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().map(|r| r.clone()).unwrap_or_default()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wakes up the listener so that it sees the stop flag
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+fn serve(mut stream: TcpStream, log: &Mutex<Vec<String>>) -> io::Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Requests are GETs, the headers are read and ignored
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+
+    let target = request_line.split_whitespace().nth(1).unwrap_or("/").to_string();
+    let (status, body) = match log.lock() {
+        Ok(mut log) => {
+            let answer = respond(&target, &log);
+            log.push(target);
+            answer
+        }
+        Err(_) => (500, Cow::Borrowed(fixtures::INTERNAL_ERROR))
+    };
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        503 => "Service Unavailable",
+        _ => "Unknown"
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, reason, body.len(), body
+    )?;
+    stream.flush()
+}
+
+/// Status and body of the answer to `target`, the requests received before it are in `log`
+///
+/// Requests sent to the server as a proxy have an absolute `target`, they are answered the same way.
+fn respond(target: &str, log: &[String]) -> (u16, Cow<'static, str>) {
+    let (status, body) = answer(target, log);
+    match (status, history_days(target)) {
+        (200, Some(body)) => (status, Cow::Owned(body)),
+        _ => (status, Cow::Borrowed(body))
+    }
+}
+
+fn answer(target: &str, log: &[String]) -> (u16, &'static str) {
+    let url = match target.starts_with('/') {
+        true => Url::parse(&format!("http://localhost{}", target)),
+        false => Url::parse(target)
+    };
+    let url = match url {
+        Ok(url) => url,
+        Err(_) => return (400, fixtures::INVALID_URL)
+    };
+    let param = |name: &str| url.query_pairs().find(|(k, _)| k == name).map(|(_, v)| v.into_owned());
+
+    match param("key").as_deref() {
+        Some(INVALID_KEY) | None => return (401, fixtures::INVALID_KEY),
+        Some(OVER_QUOTA_KEY) => return (403, fixtures::QUOTA_EXCEEDED),
+        Some(FLAKY_KEY) => {
+            let flaky = format!("key={}", FLAKY_KEY);
+            if log.iter().filter(|r| r.contains(&flaky)).count() < FLAKY_FAILURES {
+                return (503, fixtures::INTERNAL_ERROR);
+            }
+        }
+        _ => {}
+    }
+
+    let body = match url.path().trim_start_matches("/v1/") {
+        "current.json" => fixtures::CURRENT,
+        "forecast.json" => fixtures::FORECAST,
+        "history.json" => fixtures::HISTORY,
+        "future.json" => fixtures::FUTURE,
+        "search.json" => fixtures::SEARCH,
+        _ => return (400, fixtures::INVALID_URL)
+    };
+
+    match param("q").as_deref() {
+        Some(UNKNOWN_LOCATION) => (400, fixtures::NO_LOCATION),
+        _ => (200, body)
+    }
+}
+
+/// History of the days from `dt` to `end_dt`, each one a copy of the recorded day
+fn history_days(target: &str) -> Option<String> {
+    let url = Url::parse(&format!("http://localhost{}", target)).ok()?;
+    if !url.path().ends_with("/history.json") {
+        return None;
+    }
+    let param = |name: &str| url.query_pairs().find(|(k, _)| k == name).map(|(_, v)| v.into_owned());
+    let date = |name: &str| param(name)?.parse::<chrono::NaiveDate>().ok();
+
+    let dt = date("dt")?;
+    let end_dt = date("end_dt").unwrap_or(dt);
+    let mut history: serde_json::Value = serde_json::from_str(fixtures::HISTORY).ok()?;
+    let recorded = history["forecast"]["forecastday"][0].to_string();
+
+    let days = dt.iter_days()
+        .take_while(|day| *day <= end_dt)
+        .map(|day| {
+            let mut value: serde_json::Value = serde_json::from_str(&recorded.replace("2023-10-18", &day.to_string()))?;
+            value["date_epoch"] = day.and_time(chrono::NaiveTime::MIN).and_utc().timestamp().into();
+            Ok(value)
+        })
+        .collect::<Result<Vec<_>, serde_json::Error>>()
+        .ok()?;
+    history["forecast"]["forecastday"] = serde_json::Value::Array(days);
+
+    Some(history.to_string())
+}
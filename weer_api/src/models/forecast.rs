@@ -34,8 +34,13 @@ pub struct Day {
     pub avgvis_km: f32,
     pub avgvis_miles: f32,
     pub avghumidity: f32,
+    pub daily_will_it_rain: Option<u8>,
+    pub daily_chance_of_rain: Option<u32>,
+    pub daily_will_it_snow: Option<u8>,
+    pub daily_chance_of_snow: Option<u32>,
     pub condition: Condition,
-    pub uv: f32
+    pub uv: f32,
+    pub air_quality: Option<AirQuality>
 }
 
 impl Day {
@@ -99,20 +104,25 @@ pub struct Astro {
 }
 
 impl Astro {
-    pub fn sunrise(&self) -> NaiveTime {
-        NaiveTime::parse_from_str(&self.sunrise, "%m:%d %p").unwrap()
+    /// Times are `None` on days without the event, the API then sends e.g: `No moonrise`
+    fn parse_time(s: &str) -> Option<NaiveTime> {
+        NaiveTime::parse_from_str(s, "%I:%M %p").ok()
+    }
+
+    pub fn sunrise(&self) -> Option<NaiveTime> {
+        Self::parse_time(&self.sunrise)
     }
 
-    pub fn sunset(&self) -> NaiveTime {
-        NaiveTime::parse_from_str(&self.sunrise, "%m:%d %p").unwrap()
+    pub fn sunset(&self) -> Option<NaiveTime> {
+        Self::parse_time(&self.sunset)
     }
 
-    pub fn moonrise(&self) -> NaiveTime {
-        NaiveTime::parse_from_str(&self.sunrise, "%m:%d %p").unwrap()
+    pub fn moonrise(&self) -> Option<NaiveTime> {
+        Self::parse_time(&self.moonrise)
     }
 
-    pub fn moonset(&self) -> NaiveTime {
-        NaiveTime::parse_from_str(&self.sunrise, "%m:%d %p").unwrap()
+    pub fn moonset(&self) -> Option<NaiveTime> {
+        Self::parse_time(&self.moonset)
     }
 }
 
@@ -126,7 +136,7 @@ pub struct Hour {
     pub condition: Condition,
     pub wind_mph: f32,
     pub wind_kph: f32,
-    pub wind_degree: u32,
+    pub wind_degree: f32,
     pub wind_dir: String,
     pub pressure_mb: f32,
     pub pressure_in: f32,
@@ -151,6 +161,7 @@ pub struct Hour {
     pub chance_of_snow: u32,
     pub gust_mph: f32,
     pub gust_kph: f32,
+    pub uv: f32,
     pub air_quality: Option<AirQuality>
 }
 
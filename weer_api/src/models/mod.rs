@@ -8,6 +8,8 @@ use serde::{Serialize, Deserialize};
 pub use chrono::{DateTime, TimeZone, FixedOffset, ParseResult, NaiveDate, NaiveDateTime, Local};
 use std::fmt::{self, Display};
 
+use crate::ConditionCode;
+
 
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct History {
@@ -46,15 +48,15 @@ pub trait Time {
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct AirQuality {
     #[serde(rename = "co")]
-    pub carbon_monoxide: String,
+    pub carbon_monoxide: f32,
     #[serde(rename = "o3")]
-    pub ozone: String,
+    pub ozone: f32,
     #[serde(rename = "no2")]
-    pub nitrogen_dioxide: String,
+    pub nitrogen_dioxide: f32,
     #[serde(rename = "so2")]
-    pub sulphur_dioxide: String,
-    pub pm2_5: String,
-    pub pm10: String,
+    pub sulphur_dioxide: f32,
+    pub pm2_5: f32,
+    pub pm10: f32,
     #[serde(rename = "us-epa-index")]
     pub us_epa_index: u32,
     #[serde(rename = "gb-defra-index")]
@@ -66,7 +68,7 @@ pub struct AirQuality {
 pub struct Condition {
     pub text: String,
     pub icon: String,
-    pub code: u32
+    pub code: ConditionCode
 }
 
 
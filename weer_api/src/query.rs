@@ -2,7 +2,7 @@ use std::fmt::{self, Display};
 use std::net::IpAddr;
 
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Query {
     Coords(f32, f32),
     City(String),
@@ -0,0 +1,230 @@
+use std::{
+    collections::BTreeMap,
+    error::Error,
+    fmt::{self, Display},
+    fs, io, process,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError, atomic::{AtomicU64, Ordering}}
+};
+use chrono::{Datelike, Local, Months, NaiveDate};
+use serde::{Serialize, Deserialize};
+
+
+/// What a [`crate::Client`] does once the soft limit of its [`Quota`] is reached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LimitAction {
+    /// Requests fail with [`QuotaExceeded`]
+    #[default]
+    Refuse,
+    /// Requests are answered from the cache whatever the age of the stored response, the others are refused
+    CacheOnly
+}
+
+/// Calls made with an api key, persisted to a file shared by every client using it
+///
+/// Counts restart on the billing day of each month, the last day of the month when it is shorter. Keys are stored
+/// hashed, several keys can share the file. Calls are counted one at a time, by the clones of a quota as by other
+/// processes using the file.
+///
+/// # Example:
+/// ```no_run
+/// use weer_api::{Client, Quota, LimitAction};
+///
+/// let mut quota = Quota::new("/tmp/weer/quota.json");
+/// quota.budget(1_000_000)
+///     .billing_day(15)
+///     .soft_limit(90)
+///     .action(LimitAction::CacheOnly);
+///
+/// let mut client = Client::new("api_key", true);
+/// client.quota(quota);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Quota {
+    path: PathBuf,
+    budget: Option<u64>,
+    soft_limit: u8,
+    billing_day: u32,
+    action: LimitAction,
+    /// Shared by the clones, the lock of the file only keeps other processes out
+    lock: Arc<Mutex<()>>
+}
+
+/// Calls of a key in the current billing period
+#[derive(Debug, Clone, PartialEq)]
+pub struct Usage {
+    pub calls: u64,
+    pub budget: Option<u64>,
+    /// Calls after which requests are refused, only with a budget
+    pub soft_limit: Option<u64>,
+    pub period_start: NaiveDate,
+    /// First day of the next period
+    pub next_reset: NaiveDate
+}
+
+impl Usage {
+    pub fn is_limited(&self) -> bool {
+        self.soft_limit.is_some_and(|limit| self.calls >= limit)
+    }
+}
+
+/// Error of the requests refused by a [`Quota`]
+///
+/// It reaches callers as the source of a [`ureq::Transport`] error.
+#[derive(Debug)]
+pub struct QuotaExceeded {
+    pub usage: Usage
+}
+
+impl Display for QuotaExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the soft limit of the quota is reached: {} calls made since {}", self.usage.calls, self.usage.period_start)
+    }
+}
+
+impl Error for QuotaExceeded {}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Counters {
+    keys: BTreeMap<String, Counter>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Counter {
+    period_start: NaiveDate,
+    calls: u64
+}
+
+impl Quota {
+    /// Counts calls in `path`, without a budget
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            budget: None,
+            soft_limit: 90,
+            billing_day: 1,
+            action: LimitAction::default(),
+            lock: Arc::default()
+        }
+    }
+
+    /// Set up the calls allowed per billing period
+    pub fn budget(&mut self, calls: u64) -> &mut Self {
+        self.budget = Some(calls);
+        self
+    }
+
+    /// Set up the day of the month counts restart on, between 1 and 31
+    pub fn billing_day(&mut self, day: u32) -> &mut Self {
+        self.billing_day = day.clamp(1, 31);
+        self
+    }
+
+    /// Set up the percentage of the budget after which [`LimitAction`] applies, 90 by default
+    pub fn soft_limit(&mut self, percent: u8) -> &mut Self {
+        self.soft_limit = percent.min(100);
+        self
+    }
+
+    pub fn action(&mut self, action: LimitAction) -> &mut Self {
+        self.action = action;
+        self
+    }
+
+    /// File of the counters
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn limit_action(&self) -> LimitAction {
+        self.action
+    }
+
+    /// Calls made with `api_key` in the current billing period
+    pub fn usage(&self, api_key: &str) -> io::Result<Usage> {
+        self.usage_on(api_key, Local::now().date_naive())
+    }
+
+    pub(crate) fn usage_on(&self, api_key: &str, today: NaiveDate) -> io::Result<Usage> {
+        let (period_start, next_reset) = period(today, self.billing_day);
+        let calls = self.load()?.keys.get(&hash(api_key))
+            .filter(|c| c.period_start == period_start)
+            .map_or(0, |c| c.calls);
+
+        Ok(Usage {
+            calls,
+            budget: self.budget,
+            soft_limit: self.budget.map(|budget| budget * self.soft_limit as u64 / 100),
+            period_start,
+            next_reset
+        })
+    }
+
+    /// Counts a call made with `api_key`
+    pub(crate) fn count(&self, api_key: &str) -> io::Result<()> {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        // Held until the new counts are in place, released when dropped
+        let lock = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.path.with_extension("lock"))?;
+        lock.lock()?;
+
+        let (period_start, _) = period(Local::now().date_naive(), self.billing_day);
+        let mut counters = self.load()?;
+
+        let counter = counters.keys.entry(hash(api_key)).or_insert(Counter { period_start, calls: 0 });
+        if counter.period_start != period_start {
+            *counter = Counter { period_start, calls: 0 };
+        }
+        counter.calls += 1;
+
+        // Other processes read the file meanwhile, they must never see it half written
+        let n = TMP_FILES.fetch_add(1, Ordering::Relaxed);
+        let tmp = self.path.with_extension(format!("{}.{}.tmp", process::id(), n));
+        fs::write(&tmp, serde_json::to_string_pretty(&counters)?)?;
+        fs::rename(tmp, &self.path)
+    }
+
+    fn load(&self) -> io::Result<Counters> {
+        match fs::read_to_string(&self.path) {
+            Ok(s) => Ok(serde_json::from_str(&s)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Counters::default()),
+            Err(e) => Err(e)
+        }
+    }
+}
+
+/// Temporary files written by the process, to name them apart
+static TMP_FILES: AtomicU64 = AtomicU64::new(0);
+
+/// Start of the billing period `today` is in and start of the next one
+pub(crate) fn period(today: NaiveDate, billing_day: u32) -> (NaiveDate, NaiveDate) {
+    let first = today.with_day(1).unwrap_or(today);
+    let billing_date = |month_start: NaiveDate| {
+        let days = month_start.checked_add_months(Months::new(1))
+            .map_or(31, |next| next.signed_duration_since(month_start).num_days() as u32);
+        month_start.with_day(billing_day.min(days)).unwrap_or(month_start)
+    };
+
+    let this_month = billing_date(first);
+    if today >= this_month {
+        let next = first.checked_add_months(Months::new(1)).map_or(this_month, billing_date);
+        (this_month, next)
+    } else {
+        let previous = first.checked_sub_months(Months::new(1)).map_or(this_month, billing_date);
+        (previous, this_month)
+    }
+}
+
+/// Name of a key in the file, the key itself never reaches the disk
+fn hash(api_key: &str) -> String {
+    format!("{:016x}", crate::hash::fnv1a(api_key.as_bytes()))
+}
+
@@ -0,0 +1,68 @@
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant}
+};
+
+
+/// Token bucket shared by a [`crate::Client`] and its clones
+///
+/// The bucket holds up to `calls` tokens and refills at `calls` per `period`. Each request takes a token, waiting for
+/// one when the bucket is empty.
+///
+/// # Example:
+/// ```no_run
+/// use std::time::Duration;
+/// use weer_api::{Client, RateLimiter};
+///
+/// let mut client = Client::new("api_key", true);
+/// client.rate_limit(RateLimiter::new(60, Duration::from_secs(60)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    capacity: f64,
+    /// Tokens added per second
+    rate: f64,
+    bucket: Arc<Mutex<Bucket>>
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled: Instant
+}
+
+impl RateLimiter {
+    /// At most `calls` requests every `period`, the bucket starts full
+    pub fn new(calls: u32, period: Duration) -> Self {
+        let capacity = calls.max(1) as f64;
+
+        Self {
+            capacity,
+            rate: capacity / period.as_secs_f64().max(f64::EPSILON),
+            bucket: Arc::new(Mutex::new(Bucket { tokens: capacity, refilled: Instant::now() }))
+        }
+    }
+
+    /// Takes a token, waiting until there is one
+    pub(crate) fn acquire(&self) {
+        loop {
+            let wait = {
+                let Ok(mut bucket) = self.bucket.lock() else { return };
+
+                let now = Instant::now();
+                let elapsed = now.duration_since(bucket.refilled).as_secs_f64();
+                bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+                bucket.refilled = now;
+
+                if bucket.tokens >= 1.0 {
+                    bucket.tokens -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+            };
+
+            thread::sleep(wait);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::{error::Error, io};
+
+
+/// Stands for the api key wherever it would be shown
+pub(crate) const REDACTED: &str = "<redacted>";
+
+/// Parameters of a request, the `key` one redacted
+pub(crate) fn params(params: &[(&str, String)]) -> Vec<(String, String)> {
+    params.iter()
+        .map(|(name, value)| (name.to_string(), if *name == "key" { REDACTED.to_string() } else { value.clone() }))
+        .collect()
+}
+
+/// Query string of a request, the `key` parameter redacted
+pub(crate) fn query(params: &[(&str, String)]) -> String {
+    self::params(params).iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// `err` without `api_key`, which `ureq` puts in the url of its errors
+///
+/// Responses are rebuilt with their status, headers and body, their url is lost. Transport errors become [`io`]
+/// errors described without their url.
+pub(crate) fn error(err: ureq::Error, api_key: &str) -> ureq::Error {
+    if api_key.is_empty() || !err.to_string().contains(api_key) {
+        return err;
+    }
+
+    match err {
+        ureq::Error::Status(status, resp) => {
+            let mut raw = format!("HTTP/1.1 {} {}\r\n", status, resp.status_text());
+            for name in resp.headers_names() {
+                // The body below is read in full and decoded already
+                if ["content-length", "transfer-encoding", "content-encoding"].contains(&name.as_str()) {
+                    continue;
+                }
+                for value in resp.all(&name) {
+                    raw.push_str(&format!("{}: {}\r\n", name, value));
+                }
+            }
+
+            let body = match resp.into_string() {
+                Ok(body) => body,
+                Err(err) => return io::Error::new(err.kind(), text(&err.to_string(), api_key)).into()
+            };
+            raw.push_str("\r\n");
+            raw.push_str(&body);
+
+            match raw.parse::<ureq::Response>() {
+                Ok(resp) => ureq::Error::Status(status, resp),
+                Err(err) => err
+            }
+        }
+        ureq::Error::Transport(t) => {
+            let kind = t.source()
+                .and_then(|s| s.downcast_ref::<io::Error>())
+                .map_or(io::ErrorKind::Other, io::Error::kind);
+
+            let mut message = t.kind().to_string();
+            if let Some(msg) = t.message() {
+                message.push_str(&format!(": {}", msg));
+            }
+            if let Some(source) = t.source() {
+                message.push_str(&format!(": {}", source));
+            }
+
+            io::Error::new(kind, text(&message, api_key)).into()
+        }
+    }
+}
+
+fn text(s: &str, api_key: &str) -> String {
+    match api_key.is_empty() {
+        true => s.to_string(),
+        false => s.replace(api_key, REDACTED)
+    }
+}
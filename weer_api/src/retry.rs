@@ -0,0 +1,170 @@
+use std::{
+    fmt,
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    sync::Arc,
+    time::Duration
+};
+use ureq::ErrorKind;
+
+
+/// A failed attempt that is about to be retried, see [`RetryPolicy::on_retry`]
+#[derive(Debug)]
+pub struct Retry<'a> {
+    /// Number of the attempt that failed, starting at 1
+    pub attempt: u32,
+    /// Time slept before the next attempt
+    pub delay: Duration,
+    pub error: &'a ureq::Error
+}
+
+type Hook = Arc<dyn Fn(&Retry) + Send + Sync>;
+
+/// When and how long to wait before a failed request is sent again
+///
+/// Delays grow exponentially from `base_delay`, with jitter, and never exceed `max_delay`. A `retry-after` header
+/// takes precedence over the backoff, within the same limit.
+///
+/// # Example:
+/// ```no_run
+/// use std::time::Duration;
+/// use weer_api::{Client, RetryPolicy};
+///
+/// let mut policy = RetryPolicy::default();
+/// policy.max_attempts(5)
+///     .max_delay(Duration::from_secs(10))
+///     .on_retry(|retry| eprintln!("attempt {} failed, retrying in {:?}", retry.attempt, retry.delay));
+///
+/// let mut client = Client::new("api_key", true);
+/// client.retry(policy);
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    statuses: Vec<u16>,
+    transport_errors: Vec<ErrorKind>,
+    hook: Option<Hook>
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, from 1 up to 30 seconds apart, on 429 and 503 responses and on failed connections
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            statuses: vec![429, 503],
+            transport_errors: vec![ErrorKind::ConnectionFailed, ErrorKind::Io],
+            hook: None
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("jitter", &self.jitter)
+            .field("statuses", &self.statuses)
+            .field("transport_errors", &self.transport_errors)
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that sends every request once
+    pub fn never() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Set up the number of attempts, the first one included
+    pub fn max_attempts(&mut self, attempts: u32) -> &mut Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set up the delay before the first retry, it doubles on every retry
+    pub fn base_delay(&mut self, delay: Duration) -> &mut Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set up the longest delay, `retry-after` headers included
+    pub fn max_delay(&mut self, delay: Duration) -> &mut Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Set up jitter
+    ///
+    /// With jitter, which is the default, each delay is randomly picked between half and all of the backoff.
+    pub fn jitter(&mut self, jitter: bool) -> &mut Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set up the response statuses that are retried, e.g: `[429, 500, 502, 503, 504]`
+    pub fn statuses(&mut self, statuses: &[u16]) -> &mut Self {
+        self.statuses = statuses.to_vec();
+        self
+    }
+
+    /// Set up the errors of requests without a response that are retried, e.g: `[ErrorKind::Dns]`
+    pub fn transport_errors(&mut self, kinds: &[ErrorKind]) -> &mut Self {
+        self.transport_errors = kinds.to_vec();
+        self
+    }
+
+    /// Set up a function called before each retry
+    pub fn on_retry<F: Fn(&Retry) + Send + Sync + 'static>(&mut self, hook: F) -> &mut Self {
+        self.hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Delay before the attempt following `attempt`, `None` when the error is final
+    pub(crate) fn delay(&self, attempt: u32, error: &ureq::Error) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let retry_after = match error {
+            ureq::Error::Status(status, resp) if self.statuses.contains(status) => {
+                resp.header("retry-after").and_then(|h| h.trim().parse().ok()).map(Duration::from_secs)
+            }
+            ureq::Error::Transport(t) if self.transport_errors.contains(&t.kind()) => None,
+            _ => return None
+        };
+
+        let delay = retry_after.unwrap_or_else(|| {
+            let backoff = self.base_delay.saturating_mul(2u32.saturating_pow(attempt - 1));
+            match self.jitter {
+                true => backoff / 2 + backoff.mul_f64(random() / 2.0),
+                false => backoff
+            }
+        });
+
+        Some(delay.min(self.max_delay))
+    }
+
+    pub(crate) fn notify(&self, retry: &Retry) {
+        if let Some(hook) = &self.hook {
+            hook(retry)
+        }
+    }
+}
+
+/// Random number in `[0, 1)`, jitter doesn't need more than a randomly seeded hasher
+fn random() -> f64 {
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    bits as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,138 @@
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
+
+use crate::{Date, ForecastDay, Hour};
+
+
+/// Precipitation from which a day counts as rainy, in millimetres
+pub const RAINY_DAY_MM: f32 = 1.0;
+
+/// Cloud cover up to which a daytime hour counts as sunny, as percentage
+pub const SUNNY_CLOUD_COVER: u32 = 25;
+
+/// Summaries of a span of days, per month and for the whole span
+///
+/// # Example:
+/// ```no_run
+/// use weer_api::{*, chrono::{Utc, TimeZone}};
+///
+/// let client = Client::new("api_key", true);
+/// let history = client.history_range(
+///     Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap(),
+///     Utc.with_ymd_and_hms(2023, 3, 31, 0, 0, 0).unwrap()
+/// )
+///     .query(Query::City("London".to_string()))
+///     .call()
+///     .unwrap();
+///
+/// let stats = Stats::new(&history.forecast.forecast_day).unwrap();
+/// assert_eq!(3, stats.months.len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Stats {
+    /// In date order, a month with no day in the span is left out
+    pub months: Vec<Summary>,
+    pub period: Summary
+}
+
+impl Stats {
+    /// `None` without days, which are expected in date order as a [`crate::History`] has them
+    pub fn new(days: &[ForecastDay]) -> Option<Self> {
+        let period = Summary::new(days)?;
+        let months = days.chunk_by(|a, b| {
+            let (a, b) = (a.date(), b.date());
+            (a.year(), a.month()) == (b.year(), b.month())
+        })
+            .filter_map(Summary::new)
+            .collect();
+
+        Some(Self { months, period })
+    }
+}
+
+/// Aggregates of a span of days
+///
+/// Means and totals are rounded to a tenth, the precision of the API. The hourly values, gusts and sunny hours, are
+/// `None` when the days were requested for a single hour.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Summary {
+    /// First day of the span
+    pub start: NaiveDate,
+    /// Last day of the span
+    pub end: NaiveDate,
+    pub days: usize,
+    pub mean_temp_c: f32,
+    pub mean_temp_f: f32,
+    pub min_temp_c: f32,
+    pub min_temp_f: f32,
+    pub max_temp_c: f32,
+    pub max_temp_f: f32,
+    pub total_precip_mm: f32,
+    pub total_precip_in: f32,
+    /// Days with at least [`RAINY_DAY_MM`] of precipitation
+    pub rainy_days: usize,
+    pub mean_humidity: f32,
+    pub max_gust_kph: Option<f32>,
+    pub max_gust_mph: Option<f32>,
+    /// Hours between sunrise and sunset
+    pub daylight_hours: f32,
+    /// Daytime hours with at most [`SUNNY_CLOUD_COVER`] of cloud cover
+    pub sunny_hours: Option<usize>,
+    pub mean_uv: f32
+}
+
+impl Summary {
+    /// `None` without days
+    pub fn new(days: &[ForecastDay]) -> Option<Self> {
+        let first = days.first()?;
+        let last = days.last()?;
+        let hours: Vec<&Hour> = days.iter().flat_map(|fd| fd.hour.iter()).collect();
+
+        let mean = |value: &dyn Fn(&ForecastDay) -> f32| round(days.iter().map(value).sum::<f32>() / days.len() as f32);
+        let total = |value: &dyn Fn(&ForecastDay) -> f32| round(days.iter().map(value).sum());
+        let min = |value: &dyn Fn(&ForecastDay) -> f32| days.iter().map(value).fold(f32::INFINITY, f32::min);
+        let max = |value: &dyn Fn(&ForecastDay) -> f32| days.iter().map(value).fold(f32::NEG_INFINITY, f32::max);
+        let max_gust = |value: &dyn Fn(&Hour) -> f32| hours.iter().map(|h| value(h)).reduce(f32::max);
+
+        Some(Self {
+            start: first.date(),
+            end: last.date(),
+            days: days.len(),
+            mean_temp_c: mean(&|fd| fd.day.avgtemp_c),
+            mean_temp_f: mean(&|fd| fd.day.avgtemp_f),
+            min_temp_c: min(&|fd| fd.day.temp_c().min()),
+            min_temp_f: min(&|fd| fd.day.temp_f().min()),
+            max_temp_c: max(&|fd| fd.day.temp_c().max()),
+            max_temp_f: max(&|fd| fd.day.temp_f().max()),
+            total_precip_mm: total(&|fd| fd.day.totalprecip_mm),
+            total_precip_in: round_in(days.iter().map(|fd| fd.day.totalprecip_in).sum()),
+            rainy_days: days.iter().filter(|fd| fd.day.totalprecip_mm >= RAINY_DAY_MM).count(),
+            mean_humidity: mean(&|fd| fd.day.avghumidity),
+            max_gust_kph: max_gust(&|h| h.gust_kph),
+            max_gust_mph: max_gust(&|h| h.gust_mph),
+            daylight_hours: total(&daylight_hours),
+            sunny_hours: (!hours.is_empty()).then(|| {
+                hours.iter().filter(|h| h.is_day() && h.cloud <= SUNNY_CLOUD_COVER).count()
+            }),
+            mean_uv: mean(&|fd| fd.day.uv)
+        })
+    }
+}
+
+/// Hours between sunrise and sunset, 0 when the sun doesn't rise or set that day
+fn daylight_hours(fd: &ForecastDay) -> f32 {
+    match (fd.astro.sunrise(), fd.astro.sunset()) {
+        (Some(sunrise), Some(sunset)) if sunset > sunrise => (sunset - sunrise).num_minutes() as f32 / 60.0,
+        _ => 0.0
+    }
+}
+
+/// Rounded to a tenth
+pub(crate) fn round(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Inches have a hundredth of precision
+fn round_in(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
+}